@@ -1,22 +1,40 @@
+//! Prints the skeleton of a translation file for strings from `i18n.rs`.
+//!
+//! Usage:
+//!
+//! * `i18n-skeleton [strip]` - prints an empty skeleton
+//! * `i18n-skeleton merge FILE [strip]` - prints FILE with new strings appended, obsolete
+//!   and duplicate strings commented out, statistics are printed to stderr
+
 use std::{
     collections::HashSet,
-    env,
+    env, fs,
+    process::ExitCode,
     sync::{LazyLock, Mutex},
 };
 
+/// Prefix of translations for strings removed from `i18n.rs`.
+const OBSOLETE: &str = "// OBSOLETE: ";
+/// Prefix of repeated translations of the same string.
+const DUPLICATE: &str = "// DUPLICATE: ";
+/// Header of strings appended by the merge.
+const NEW_STRINGS: &str = "// new strings";
+
 struct State {
     strip: bool,
     map: HashSet<&'static str>,
+    list: Vec<&'static str>,
 }
 
 static STATE: LazyLock<Mutex<State>> = LazyLock::new(|| {
     Mutex::new(State {
-        strip: env::args().nth(1).is_some_and(|i| i.starts_with("strip")),
+        strip: env::args().skip(1).any(|i| i == "strip"),
         map: HashSet::new(),
+        list: Vec::new(),
     })
 });
 
-fn print(mut i: &'static str) {
+fn add(mut i: &'static str) {
     let mut state = STATE.lock().unwrap();
     if let Some(s) = i.strip_prefix('#') {
         if state.strip {
@@ -26,11 +44,16 @@ fn print(mut i: &'static str) {
     }
     if !i.is_empty() && !state.map.contains(i) {
         state.map.insert(i);
-        let pad = 32_usize.saturating_sub(i.len());
-        println!("\"{i}\"{:pad$}\"\"", ' ');
+        state.list.push(i);
     }
 }
-fn main() {
+
+fn print_token(key: &str) {
+    let pad = 32_usize.saturating_sub(key.len());
+    println!("\"{key}\"{:pad$}\"\"", ' ');
+}
+
+fn print_skeleton(list: &[&str]) {
     println!("// How to work with this file:");
     println!(
         "// 1) This file must be called maintui_x.txt, where x is your language in lower case"
@@ -45,7 +68,121 @@ fn main() {
     println!("\"Language\" \"<YOUR_LANGUAGE_HERE>\"");
     println!("\"Tokens\"");
     println!("{{");
+    for i in list {
+        print_token(i);
+    }
+    println!("}}");
+    println!("}}");
+}
+
+/// Returns the content of the quoted string at the start of `s` and the rest of `s`.
+fn parse_quoted(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('"')?;
+    let mut escape = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escape => escape = false,
+            '\\' => escape = true,
+            '"' => return Some((&s[..i], &s[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a line in the form `"key" "value"`.
+fn parse_token(line: &str) -> Option<(&str, &str)> {
+    let (key, rest) = parse_quoted(line)?;
+    let (value, _) = parse_quoted(rest)?;
+    Some((key, value))
+}
+
+#[derive(Default)]
+struct Stats {
+    translated: usize,
+    untranslated: usize,
+    new: usize,
+    obsolete: usize,
+    duplicate: usize,
+}
+
+fn merge(path: &str, list: &[&str]) -> Result<Stats, String> {
+    let src = fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    let mut lines = src.lines();
+    let mut stats = Stats::default();
 
+    // header with comments and the language name
+    let mut depth = 0;
+    for line in lines.by_ref() {
+        println!("{line}");
+        depth += line.matches('{').count();
+        if depth == 2 {
+            break;
+        }
+    }
+    if depth != 2 {
+        return Err(format!("{path}: \"Tokens\" block is not found"));
+    }
+
+    let mut found = HashSet::new();
+    let mut has_header = false;
+    for line in lines.by_ref() {
+        if line.trim_start().starts_with('}') {
+            // append new strings to the end of the block
+            let new: Vec<_> = list.iter().filter(|i| !found.contains(*i)).collect();
+            if !new.is_empty() {
+                // reuse the header added by the previous merge
+                if !has_header {
+                    println!("{NEW_STRINGS}");
+                }
+                for i in new {
+                    print_token(i);
+                }
+            }
+            stats.new = list.len() - found.len();
+            println!("{line}");
+            break;
+        }
+        let obsolete = line.trim_start().strip_prefix(OBSOLETE);
+        let Some((key, value)) = parse_token(obsolete.unwrap_or(line)) else {
+            if line.trim() == NEW_STRINGS {
+                has_header = true;
+            } else if !line.trim().is_empty() && !line.trim_start().starts_with("//") {
+                eprintln!("{path}: unknown line \"{line}\"");
+            }
+            println!("{line}");
+            continue;
+        };
+        if found.contains(key) {
+            eprintln!("{path}: duplicate string \"{key}\"");
+            stats.duplicate += 1;
+            println!("{DUPLICATE}{}", obsolete.unwrap_or(line));
+        } else if list.contains(&key) {
+            found.insert(key);
+            if value.is_empty() {
+                stats.untranslated += 1;
+            } else {
+                stats.translated += 1;
+            }
+            // restore the string if it was added back
+            println!("{}", obsolete.unwrap_or(line));
+        } else {
+            stats.obsolete += 1;
+            if obsolete.is_some() {
+                println!("{line}");
+            } else {
+                println!("{OBSOLETE}{line}");
+            }
+        }
+    }
+
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(stats)
+}
+
+fn main() -> ExitCode {
     macro_rules! define_strings {
         ($($name:ident { $($body:tt)* })*) => ({
             $(
@@ -53,11 +190,43 @@ fn main() {
             )*
         });
         ($($name:ident = $value:expr),* $(,)?) => {
-            $(print($value);)*
+            $(add($value);)*
         };
     }
     include!("../xash3d-maintui/src/i18n.rs");
 
-    println!("}}");
-    println!("}}");
+    let list = STATE.lock().unwrap().list.clone();
+    let mut args = env::args().skip(1).filter(|i| i != "strip");
+    match args.next().as_deref() {
+        None => print_skeleton(&list),
+        Some("merge") => {
+            let Some(path) = args.next() else {
+                eprintln!("usage: i18n-skeleton merge FILE [strip]");
+                return ExitCode::FAILURE;
+            };
+            match merge(&path, &list) {
+                Ok(stats) => {
+                    let total = stats.translated + stats.untranslated + stats.new;
+                    eprintln!(
+                        "{path}: {}/{total} translated, {} untranslated, {} new, {} obsolete, \
+                         {} duplicate",
+                        stats.translated,
+                        stats.untranslated,
+                        stats.new,
+                        stats.obsolete,
+                        stats.duplicate
+                    );
+                }
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some(arg) => {
+            eprintln!("unknown argument \"{arg}\"");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
 }
@@ -6,3 +6,16 @@ scriptroot=$(dirname "$script")
 cd "$scriptroot"
 cargo run --bin i18n-skeleton -- > ../data/maintui_LANG_all.txt || exit 1
 cargo run --bin i18n-skeleton -- strip > ../data/maintui_LANG_stripped.txt || exit 1
+
+# merge new strings into existing translations
+for file in ../data/maintui_*.txt; do
+    case "$file" in
+        *_LANG_*) continue ;;
+    esac
+    [ -f "$file" ] || continue
+    # stripped translations take GameUI strings from the game files
+    strip=
+    grep -q '^[[:space:]]*"GameUI_' "$file" || strip=strip
+    cargo run --bin i18n-skeleton -- merge "$file" $strip > "$file.new" || exit 1
+    mv "$file.new" "$file"
+done
@@ -0,0 +1,16 @@
+//! Preview maintui menus in a terminal.
+//!
+//! Usage: maintui-preview [GAME_DIR]
+
+use std::{env, path::PathBuf, process::ExitCode};
+
+fn main() -> ExitCode {
+    let game_dir = env::args_os().nth(1).map(PathBuf::from);
+    match menu::preview::run(game_dir) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("maintui-preview: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -14,7 +14,8 @@ use xash3d_ratatui::XashBackend;
 use crate::{
     input::{Key, KeyEvent},
     ui::{
-        Control, Screen, State, symbols,
+        Control, Screen, State, animation, symbols,
+        theme::theme,
         utils::{self, Scroll},
     },
     widgets::{ListState, Scrollbar},
@@ -83,6 +84,14 @@ impl ConfigList {
         ret
     }
 
+    pub fn selected(&self) -> Option<usize> {
+        self.list_state.selected()
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.list_state.select(index);
+    }
+
     pub fn is_grab_input(&self) -> bool {
         matches!(self.state.focus(), Focus::Grab(_))
     }
@@ -168,11 +177,11 @@ impl ConfigList {
             let [point_area, content_area] = layout.areas(item_area);
             let mut style = Style::default();
             if self.list_state.selected() == Some(i) {
-                style = style.add_modifier(Modifier::BOLD).black();
-                match self.state.focus() {
-                    Focus::Main => style = style.on_yellow(),
-                    Focus::Grab(..) => style = style.on_green(),
+                style = match self.state.focus() {
+                    Focus::Main => theme().highlight,
+                    Focus::Grab(..) => animation::pulse(theme().grabbed, screen.time),
                 };
+                style = style.add_modifier(Modifier::BOLD);
                 Line::raw(symbols::HIGHLIGHT_SYMBOL)
                     .style(style)
                     .render(point_area, buf);
@@ -9,7 +9,7 @@ use xash3d_ui::cvar::{GetCvar, SetCvar};
 
 use crate::{
     input::{Key, KeyEvent},
-    ui::{Screen, sound},
+    ui::{Screen, bidi, sound},
     widgets::{Checkbox, ConfirmResult, Input, ListPopup, SelectResult, Slider, Value, WidgetMut},
 };
 
@@ -146,11 +146,17 @@ impl<V, T> ConfigEntry<V, T> {
     fn render_label_impl(&self, area: Rect, buf: &mut Buffer, label: &str, style: Style) {
         let width = area.width.saturating_sub(1) as usize;
         let line = match label.char_indices().nth(width) {
+            // right-to-left labels are truncated on the left side
+            Some((i, _)) if bidi::is_rtl(label) => Line::from_iter([
+                Span::from("<").style(Style::new().dark_gray()),
+                Span::from(bidi::reorder(&label[..i])),
+            ])
+            .right_aligned(),
             Some((i, _)) => Line::from_iter([
-                Span::from(&label[..i]),
+                Span::from(bidi::reorder(&label[..i])),
                 Span::from(">").style(Style::new().dark_gray()),
             ]),
-            None => Line::raw(label),
+            None => bidi::line(label),
         };
         line.style(style).render(area, buf);
     }
@@ -450,3 +456,40 @@ impl ConfigItem for ConfigEntry<CompactString, Input> {
         self.widget.mouse_event(backend)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+
+    use super::*;
+
+    struct Fixed;
+
+    impl ConfigBackend<bool> for Fixed {
+        fn read(&self) -> Option<bool> {
+            None
+        }
+
+        fn write(&mut self, _: bool) {}
+    }
+
+    fn render_label(label: &str, width: u16) -> String {
+        let entry = ConfigEntry::checkbox().build(Fixed);
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, 1));
+        entry.render_label_impl(buf.area, &mut buf, label, Style::new());
+        (0..width).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    #[test]
+    fn truncate_rtl_label() {
+        // "אבגדהו" (alef, bet, gimel, dalet, he, vav)
+        let label = "\u{5d0}\u{5d1}\u{5d2}\u{5d3}\u{5d4}\u{5d5}";
+        // the end of the text is cut on the left side
+        assert_eq!(render_label(label, 5), "<\u{5d3}\u{5d2}\u{5d1}\u{5d0}");
+        assert_eq!(
+            render_label(label, 8),
+            "  \u{5d5}\u{5d4}\u{5d3}\u{5d2}\u{5d1}\u{5d0}"
+        );
+        assert_eq!(render_label("abcdef", 5), "abcd>");
+    }
+}
@@ -0,0 +1,884 @@
+//! Fake engine for running menus without Xash3D.
+//!
+//! The engine function tables are filled with stubs backed by an in-memory state. Cvars,
+//! files and key bindings can be prepared before a menu is created and every client
+//! command, played sound or saved file is recorded for later inspection.
+
+use core::{
+    ffi::{CStr, c_char, c_int, c_uint, c_void},
+    mem, ptr, slice, str,
+};
+use std::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    ffi::CString,
+    fs,
+    path::PathBuf,
+    string::{String, ToString},
+    sync::{Mutex, MutexGuard, Once},
+    vec::Vec,
+};
+
+use hashbrown::HashMap;
+use xash3d_ui::ffi::{
+    common::{cvar_s, gameinfo2_s, netadr_s},
+    menu::{
+        MENU_EXTENDED_API_VERSION, UI_EXTENDED_FUNCTIONS, UI_FUNCTIONS, net_api_s,
+        ui_enginefuncs_s, ui_extendedfuncs_s, ui_globalvars_s,
+    },
+};
+
+use crate::ui::Ui;
+
+pub const SCREEN_WIDTH: c_int = 1280;
+pub const SCREEN_HEIGHT: c_int = 720;
+
+const GAME_DIR: &str = "valve";
+const GAME_TITLE: &str = "Half-Life";
+const START_MAP: &str = "c0a0";
+const TRAIN_MAP: &str = "t0a0";
+
+const VIDEO_MODES: &[&CStr] = &[c"640x480", c"800x600", c"1024x768", c"1280x720"];
+
+pub struct Engine {
+    /// Console variables.
+    pub cvars: HashMap<String, CString>,
+    /// Files in the game directory.
+    pub files: HashMap<String, Vec<u8>>,
+    /// Optional game directory on disk used for files missing in `files`.
+    pub game_dir: Option<PathBuf>,
+    /// Key bindings by key number.
+    pub bindings: HashMap<c_int, CString>,
+    /// Commands registered with `add_command`.
+    pub registered: HashMap<String, unsafe extern "C" fn()>,
+    /// Arguments of the executed console command.
+    pub args: Vec<CString>,
+    /// Commands sent to the client in order of appearance.
+    pub commands: Vec<String>,
+    /// Played sounds in order of appearance.
+    pub sounds: Vec<String>,
+    pub key_dest: c_int,
+    pub in_game: bool,
+    /// Saving files fails if set.
+    pub read_only: bool,
+    pub clipboard: Option<CString>,
+    pictures: HashMap<String, Picture>,
+    next_picture: c_int,
+    addresses: Vec<CString>,
+    files_list: Vec<CString>,
+    files_list_ptrs: Vec<*mut c_char>,
+    key_name: CString,
+}
+
+// SAFETY: raw pointers are only used to keep the engine owned strings alive
+unsafe impl Send for Engine {}
+
+#[derive(Copy, Clone)]
+struct Picture {
+    id: c_int,
+    width: c_int,
+    height: c_int,
+}
+
+impl Engine {
+    fn new() -> Self {
+        let mut engine = Self {
+            cvars: HashMap::new(),
+            files: HashMap::new(),
+            game_dir: None,
+            bindings: HashMap::new(),
+            registered: HashMap::new(),
+            args: Vec::new(),
+            commands: Vec::new(),
+            sounds: Vec::new(),
+            key_dest: 0,
+            in_game: false,
+            read_only: false,
+            clipboard: None,
+            pictures: HashMap::new(),
+            next_picture: 1,
+            addresses: Vec::new(),
+            files_list: Vec::new(),
+            files_list_ptrs: Vec::new(),
+            key_name: CString::default(),
+        };
+        engine.set_cvar("host_allow_changegame", "0");
+        engine.set_cvar("ui_language", "english");
+        engine
+    }
+
+    pub fn set_cvar(&mut self, name: &str, value: &str) {
+        self.cvars
+            .insert(name.to_owned(), CString::new(value).unwrap());
+    }
+
+    pub fn cvar(&self, name: &str) -> Option<&str> {
+        self.cvars.get(name).map(|i| i.to_str().unwrap())
+    }
+
+    pub fn add_file(&mut self, path: &str, data: impl Into<Vec<u8>>) {
+        self.files.insert(path.to_owned(), data.into());
+    }
+
+    /// Returns `true` if the picture is created and not freed yet.
+    pub fn has_picture(&self, name: &str) -> bool {
+        self.pictures.contains_key(name)
+    }
+
+    fn read_file(&self, path: &str) -> Option<Cow<'_, [u8]>> {
+        if let Some(data) = self.files.get(path) {
+            return Some(Cow::Borrowed(data));
+        }
+        let root = self.game_dir.as_ref()?;
+        fs::read(root.join(path)).ok().map(Cow::Owned)
+    }
+
+    fn file_exists(&self, path: &str) -> bool {
+        self.files.contains_key(path)
+            || self
+                .game_dir
+                .as_ref()
+                .is_some_and(|root| root.join(path).is_file())
+    }
+
+    fn list_files(&self, pattern: &str) -> Vec<String> {
+        let pattern = pattern.as_bytes();
+        let mut list: Vec<String> = self
+            .files
+            .keys()
+            .filter(|i| wildcard_match(pattern, i.as_bytes()))
+            .cloned()
+            .collect();
+
+        if let Some(root) = &self.game_dir {
+            let pattern = str::from_utf8(pattern).unwrap();
+            let dir = pattern.rsplit_once('/').map_or("", |(dir, _)| dir);
+            if let Ok(entries) = fs::read_dir(root.join(dir)) {
+                for entry in entries.flatten() {
+                    let name = entry.file_name();
+                    let Some(name) = name.to_str() else { continue };
+                    let path = if dir.is_empty() {
+                        name.to_owned()
+                    } else {
+                        std::format!("{dir}/{name}")
+                    };
+                    if wildcard_match(pattern.as_bytes(), path.as_bytes()) {
+                        list.push(path);
+                    }
+                }
+            }
+        }
+
+        list.sort();
+        list.dedup();
+        list
+    }
+
+    fn cvar_c_str(&self, name: &CStr) -> *const c_char {
+        let name = name.to_string_lossy();
+        match self.cvars.get(name.as_ref()) {
+            Some(value) => value.as_ptr(),
+            None => c"".as_ptr(),
+        }
+    }
+
+    fn address(&mut self, s: &CStr) -> u32 {
+        match self.addresses.iter().position(|i| i.as_c_str() == s) {
+            Some(index) => index as u32,
+            None => {
+                self.addresses.push(s.to_owned());
+                self.addresses.len() as u32 - 1
+            }
+        }
+    }
+}
+
+static ENGINE: Mutex<Option<Engine>> = Mutex::new(None);
+
+static mut ENGINE_FUNCS: ui_enginefuncs_s = unsafe { mem::zeroed() };
+static mut EXTENDED_FUNCS: ui_extendedfuncs_s = unsafe { mem::zeroed() };
+static mut NET_API: net_api_s = unsafe { mem::zeroed() };
+static mut GLOBALS: ui_globalvars_s = unsafe { mem::zeroed() };
+static mut GAME_INFO: gameinfo2_s = unsafe { mem::zeroed() };
+
+unsafe extern "C" {
+    fn GetMenuAPI(
+        functions: *mut UI_FUNCTIONS,
+        engfuncs: *mut ui_enginefuncs_s,
+        globals: *mut ui_globalvars_s,
+    ) -> c_int;
+
+    fn GetExtAPI(
+        version: c_int,
+        functions: *mut UI_EXTENDED_FUNCTIONS,
+        engfuncs: *mut ui_extendedfuncs_s,
+    ) -> c_int;
+}
+
+/// Installs the fake engine.
+///
+/// Can be called multiple times, the engine is initialized only once and the state is
+/// reset on every call.
+pub fn init() {
+    static INIT: Once = Once::new();
+
+    *ENGINE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Engine::new());
+
+    INIT.call_once(|| unsafe {
+        fill_engine_funcs(&mut *ptr::addr_of_mut!(ENGINE_FUNCS));
+        fill_extended_funcs(&mut *ptr::addr_of_mut!(EXTENDED_FUNCS));
+        fill_net_api(&mut *ptr::addr_of_mut!(NET_API));
+        (*ptr::addr_of_mut!(EXTENDED_FUNCS)).pNetAPI = ptr::addr_of_mut!(NET_API);
+        fill_game_info(&mut *ptr::addr_of_mut!(GAME_INFO));
+
+        let mut functions: UI_FUNCTIONS = mem::zeroed();
+        let mut ext_functions: UI_EXTENDED_FUNCTIONS = mem::zeroed();
+        GetMenuAPI(
+            &mut functions,
+            ptr::addr_of_mut!(ENGINE_FUNCS),
+            ptr::addr_of_mut!(GLOBALS),
+        );
+        GetExtAPI(
+            MENU_EXTENDED_API_VERSION as c_int,
+            &mut ext_functions,
+            ptr::addr_of_mut!(EXTENDED_FUNCS),
+        );
+    });
+
+    set_screen_size(SCREEN_WIDTH, SCREEN_HEIGHT);
+    set_time(0.0);
+}
+
+/// Returns the fake engine state.
+pub fn state() -> MutexGuard<'static, Option<Engine>> {
+    ENGINE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Calls `f` with the fake engine state.
+///
+/// The lock is not held while menus are running, so it is safe to use it between calls
+/// to [Ui](crate::ui::Ui) methods.
+pub fn with<R>(f: impl FnOnce(&mut Engine) -> R) -> R {
+    f(state().as_mut().expect("fake engine is not initialized"))
+}
+
+pub fn set_screen_size(width: c_int, height: c_int) {
+    unsafe {
+        let globals = &mut *ptr::addr_of_mut!(GLOBALS);
+        globals.scrWidth = width;
+        globals.scrHeight = height;
+    }
+}
+
+/// Changes the screen size to fit the grid of cells.
+///
+/// The automatic font size depends on the screen size so the current size is saved to
+/// ui_font_size first. Returns `false` if the grid size cannot be reached.
+pub fn resize_grid(ui: &mut Ui, columns: u16, rows: u16) -> bool {
+    let size = ui.terminal().backend().get_font_size().to_string();
+    with(|engine| {
+        let current = engine
+            .cvar("ui_font_size")
+            .and_then(|i| i.parse::<f32>().ok());
+        if current.unwrap_or(0.0) == 0.0 {
+            engine.set_cvar("ui_font_size", &size);
+        }
+    });
+    for _ in 0..4 {
+        let cell = ui.terminal().backend().cell_size_in_pixels();
+        set_screen_size(
+            columns as c_int * cell.width as c_int,
+            rows as c_int * cell.height as c_int,
+        );
+        ui.vid_init();
+        let size = ui.terminal().backend().size();
+        if size.width == columns && size.height == rows {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn set_time(time: f32) {
+    unsafe {
+        (*ptr::addr_of_mut!(GLOBALS)).time = time;
+    }
+}
+
+pub fn time() -> f32 {
+    unsafe { (*ptr::addr_of!(GLOBALS)).time }
+}
+
+fn copy_str(dst: &mut [c_char], src: &str) {
+    let len = src.len().min(dst.len() - 1);
+    for (d, s) in dst.iter_mut().zip(&src.as_bytes()[..len]) {
+        *d = *s as c_char;
+    }
+    dst[len] = 0;
+}
+
+unsafe fn c_str<'a>(s: *const c_char) -> &'a CStr {
+    if s.is_null() {
+        c""
+    } else {
+        unsafe { CStr::from_ptr(s) }
+    }
+}
+
+unsafe fn string(s: *const c_char) -> String {
+    unsafe { c_str(s) }.to_string_lossy().into_owned()
+}
+
+fn wildcard_match(pattern: &[u8], s: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => s.is_empty(),
+        Some((b'*', rest)) => (0..=s.len()).any(|i| wildcard_match(rest, &s[i..])),
+        Some((c, rest)) => match s.split_first() {
+            Some((x, tail)) if x.eq_ignore_ascii_case(c) => wildcard_match(rest, tail),
+            _ => false,
+        },
+    }
+}
+
+fn bmp_size(data: &[u8]) -> (c_int, c_int) {
+    if data.len() >= 26 && data.starts_with(b"BM") {
+        let width = i32::from_le_bytes(data[18..22].try_into().unwrap());
+        let height = i32::from_le_bytes(data[22..26].try_into().unwrap());
+        (width, height.abs())
+    } else {
+        (0, 0)
+    }
+}
+
+fn fill_game_info(info: &mut gameinfo2_s) {
+    copy_str(&mut info.gamefolder, GAME_DIR);
+    copy_str(&mut info.title, GAME_TITLE);
+    copy_str(&mut info.startmap, START_MAP);
+    copy_str(&mut info.trainmap, TRAIN_MAP);
+}
+
+mod funcs {
+    use super::*;
+
+    pub unsafe extern "C" fn pic_load(
+        name: *const c_char,
+        data: *const u8,
+        len: c_int,
+        _flags: c_int,
+    ) -> c_int {
+        let name = unsafe { string(name) };
+        let data = if data.is_null() {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(data, len as usize) }
+        };
+        with(|engine| {
+            if let Some(pic) = engine.pictures.get(&name) {
+                return pic.id;
+            }
+            if data.is_empty() && !engine.file_exists(&name) {
+                return 0;
+            }
+            let (width, height) = bmp_size(data);
+            let id = engine.next_picture;
+            engine.next_picture += 1;
+            let pic = Picture { id, width, height };
+            engine.pictures.insert(name, pic);
+            id
+        })
+    }
+
+    pub unsafe extern "C" fn pic_free(name: *const c_char) {
+        let name = unsafe { string(name) };
+        with(|engine| engine.pictures.remove(&name));
+    }
+
+    fn pic_size(id: c_int) -> (c_int, c_int) {
+        with(|engine| {
+            engine
+                .pictures
+                .values()
+                .find(|i| i.id == id)
+                .map_or((0, 0), |i| (i.width, i.height))
+        })
+    }
+
+    pub unsafe extern "C" fn pic_width(id: c_int) -> c_int {
+        pic_size(id).0
+    }
+
+    pub unsafe extern "C" fn pic_height(id: c_int) -> c_int {
+        pic_size(id).1
+    }
+
+    pub unsafe extern "C" fn pic_set(_id: c_int, _r: c_int, _g: c_int, _b: c_int, _a: c_int) {}
+
+    pub unsafe extern "C" fn pic_draw(
+        _x: c_int,
+        _y: c_int,
+        _width: c_int,
+        _height: c_int,
+        _rect: *const c_void,
+    ) {
+    }
+
+    pub unsafe extern "C" fn enable_scissor(_x: c_int, _y: c_int, _width: c_int, _height: c_int) {}
+
+    pub unsafe extern "C" fn disable_scissor() {}
+
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe extern "C" fn fill_rgba(
+        _x: c_int,
+        _y: c_int,
+        _width: c_int,
+        _height: c_int,
+        _r: c_int,
+        _g: c_int,
+        _b: c_int,
+        _a: c_int,
+    ) {
+    }
+
+    pub unsafe extern "C" fn register_variable(
+        name: *const c_char,
+        value: *const c_char,
+        _flags: c_int,
+    ) -> *mut cvar_s {
+        let name = unsafe { string(name) };
+        let value = unsafe { c_str(value) }.to_owned();
+        with(|engine| engine.cvars.entry(name).or_insert(value));
+        Box::leak(Box::new(unsafe { mem::zeroed::<cvar_s>() }))
+    }
+
+    pub unsafe extern "C" fn get_cvar_float(name: *const c_char) -> f32 {
+        let name = unsafe { string(name) };
+        with(|engine| {
+            engine
+                .cvar(&name)
+                .and_then(|i| i.trim().parse().ok())
+                .unwrap_or(0.0)
+        })
+    }
+
+    pub unsafe extern "C" fn get_cvar_string(name: *const c_char) -> *const c_char {
+        let name = unsafe { c_str(name) };
+        with(|engine| engine.cvar_c_str(name))
+    }
+
+    pub unsafe extern "C" fn cvar_set_string(name: *const c_char, value: *const c_char) {
+        let name = unsafe { string(name) };
+        let value = unsafe { string(value) };
+        with(|engine| engine.set_cvar(&name, &value));
+    }
+
+    pub unsafe extern "C" fn cvar_set_value(name: *const c_char, value: f32) {
+        let name = unsafe { string(name) };
+        with(|engine| engine.set_cvar(&name, &value.to_string()));
+    }
+
+    pub unsafe extern "C" fn add_command(
+        name: *const c_char,
+        func: Option<unsafe extern "C" fn()>,
+    ) -> c_int {
+        let name = unsafe { string(name) };
+        match func {
+            Some(func) => {
+                with(|engine| engine.registered.insert(name, func));
+                1
+            }
+            None => 0,
+        }
+    }
+
+    pub unsafe extern "C" fn del_command(name: *const c_char) {
+        let name = unsafe { string(name) };
+        with(|engine| engine.registered.remove(&name));
+    }
+
+    pub unsafe extern "C" fn cmd_argc() -> c_int {
+        with(|engine| engine.args.len() as c_int)
+    }
+
+    pub unsafe extern "C" fn cmd_argv(n: c_int) -> *const c_char {
+        with(|engine| match engine.args.get(n as usize) {
+            Some(arg) => arg.as_ptr(),
+            None => c"".as_ptr(),
+        })
+    }
+
+    pub unsafe extern "C" fn client_cmd(_execute_now: c_int, cmd: *const c_char) {
+        let cmd = unsafe { string(cmd) };
+        let cmd = cmd.trim_end_matches('\n');
+        with(|engine| {
+            engine
+                .commands
+                .extend(cmd.split('\n').map(|i| i.to_owned()))
+        });
+    }
+
+    pub unsafe extern "C" fn play_local_sound(name: *const c_char) {
+        let name = unsafe { string(name) };
+        with(|engine| engine.sounds.push(name));
+    }
+
+    pub unsafe extern "C" fn file_exists(name: *const c_char, _gamedir_only: c_int) -> c_int {
+        let name = unsafe { string(name) };
+        with(|engine| engine.file_exists(&name) as c_int)
+    }
+
+    pub unsafe extern "C" fn get_game_dir(out: *mut c_char) {
+        let out = unsafe { slice::from_raw_parts_mut(out, 64) };
+        copy_str(out, GAME_DIR);
+    }
+
+    pub unsafe extern "C" fn create_maps_list(_refresh: c_int) -> c_int {
+        with(|engine| engine.file_exists("maps.lst") as c_int)
+    }
+
+    pub unsafe extern "C" fn client_in_game() -> c_int {
+        with(|engine| engine.in_game as c_int)
+    }
+
+    pub unsafe extern "C" fn client_join(_addr: netadr_s) {
+        with(|engine| engine.commands.push("<join>".to_owned()));
+    }
+
+    pub unsafe extern "C" fn get_player_model() -> *mut c_void {
+        ptr::null_mut()
+    }
+
+    pub unsafe extern "C" fn set_model(_ent: *mut c_void, _path: *const c_char) {}
+
+    pub unsafe extern "C" fn clear_scene() {}
+
+    pub unsafe extern "C" fn render_scene(_view: *const c_void) {}
+
+    pub unsafe extern "C" fn create_visible_entity(_type: c_int, _ent: *mut c_void) -> c_int {
+        0
+    }
+
+    pub unsafe extern "C" fn load_file(name: *const c_char, len: *mut c_int) -> *mut u8 {
+        let name = unsafe { string(name) };
+        with(|engine| {
+            let Some(data) = engine.read_file(&name) else {
+                return ptr::null_mut();
+            };
+            unsafe {
+                // zero terminated like in the engine
+                let buf = libc::malloc(data.len() + 1) as *mut u8;
+                ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len());
+                *buf.add(data.len()) = 0;
+                if !len.is_null() {
+                    *len = data.len() as c_int;
+                }
+                buf
+            }
+        })
+    }
+
+    pub unsafe extern "C" fn free_file(buf: *mut c_void) {
+        unsafe { libc::free(buf) }
+    }
+
+    pub unsafe extern "C" fn save_file(
+        name: *const c_char,
+        data: *const c_void,
+        len: c_int,
+    ) -> c_int {
+        let name = unsafe { string(name) };
+        let data = unsafe { slice::from_raw_parts(data as *const u8, len as usize) };
+        with(|engine| {
+            if engine.read_only {
+                return 0;
+            }
+            engine.add_file(&name, data);
+            1
+        })
+    }
+
+    pub unsafe extern "C" fn remove_file(name: *const c_char) -> c_int {
+        let name = unsafe { string(name) };
+        with(|engine| engine.files.remove(&name).is_some() as c_int)
+    }
+
+    pub unsafe extern "C" fn key_clear_states() {}
+
+    pub unsafe extern "C" fn set_key_dest(dest: c_int) {
+        with(|engine| engine.key_dest = dest);
+    }
+
+    pub unsafe extern "C" fn keynum_to_string(keynum: c_int) -> *const c_char {
+        with(|engine| {
+            let s = match keynum as u8 {
+                c @ b'a'..=b'z' | c @ b'0'..=b'9' => (c as char).to_string(),
+                _ => std::format!("KEY{keynum}"),
+            };
+            engine.key_name = CString::new(s).unwrap();
+            engine.key_name.as_ptr()
+        })
+    }
+
+    pub unsafe extern "C" fn key_get_binding(keynum: c_int) -> *const c_char {
+        with(|engine| match engine.bindings.get(&keynum) {
+            Some(binding) => binding.as_ptr(),
+            None => ptr::null(),
+        })
+    }
+
+    pub unsafe extern "C" fn key_set_binding(keynum: c_int, binding: *const c_char) {
+        let binding = unsafe { c_str(binding) }.to_owned();
+        with(|engine| engine.bindings.insert(keynum, binding));
+    }
+
+    pub unsafe extern "C" fn key_is_down(_keynum: c_int) -> c_int {
+        0
+    }
+
+    pub unsafe extern "C" fn key_get_state(_name: *const c_char) -> *mut c_void {
+        ptr::null_mut()
+    }
+
+    pub unsafe extern "C" fn get_files_list(
+        pattern: *const c_char,
+        count: *mut c_int,
+        _gamedir_only: c_int,
+    ) -> *mut *mut c_char {
+        let pattern = unsafe { string(pattern) };
+        with(|engine| {
+            engine.files_list = engine
+                .list_files(&pattern)
+                .into_iter()
+                .map(|i| CString::new(i).unwrap())
+                .collect();
+            engine.files_list_ptrs = engine
+                .files_list
+                .iter()
+                .map(|i| i.as_ptr() as *mut c_char)
+                .collect();
+            engine.files_list_ptrs.push(ptr::null_mut());
+            if !count.is_null() {
+                unsafe { *count = engine.files_list.len() as c_int };
+            }
+            engine.files_list_ptrs.as_mut_ptr()
+        })
+    }
+
+    pub unsafe extern "C" fn get_save_comment(_name: *const c_char, comment: *mut c_char) -> c_int {
+        unsafe { *comment = 0 };
+        0
+    }
+
+    pub unsafe extern "C" fn check_game_dll() -> c_int {
+        1
+    }
+
+    pub unsafe extern "C" fn get_clipboard_data() -> *mut c_char {
+        with(|engine| match &engine.clipboard {
+            Some(s) => s.as_ptr() as *mut c_char,
+            None => ptr::null_mut(),
+        })
+    }
+
+    pub unsafe extern "C" fn write_server_config(_name: *const c_char) {}
+
+    pub unsafe extern "C" fn play_background_track(_intro: *const c_char, _loop: *const c_char) {}
+
+    pub unsafe extern "C" fn host_end_game(_message: *const c_char) {
+        with(|engine| engine.commands.push("<end game>".to_owned()));
+    }
+
+    pub unsafe extern "C" fn random_float(low: f32, _high: f32) -> f32 {
+        low
+    }
+
+    pub unsafe extern "C" fn random_long(low: c_int, _high: c_int) -> c_int {
+        low
+    }
+
+    pub unsafe extern "C" fn is_map_valid(name: *mut c_char) -> c_int {
+        let name = unsafe { string(name) };
+        let path = std::format!("maps/{name}.bsp");
+        with(|engine| engine.file_exists(&path) as c_int)
+    }
+
+    pub unsafe extern "C" fn process_image(
+        _texnum: c_int,
+        _gamma: f32,
+        _top_color: c_int,
+        _bottom_color: c_int,
+    ) {
+    }
+
+    pub unsafe extern "C" fn get_mode_string(mode: c_int) -> *const c_char {
+        match VIDEO_MODES.get(mode as usize) {
+            Some(s) => s.as_ptr(),
+            None => ptr::null(),
+        }
+    }
+
+    pub unsafe extern "C" fn enable_text_input(_enable: c_int) {}
+
+    pub unsafe extern "C" fn get_renderers(
+        num: c_uint,
+        short_name: *mut c_char,
+        size1: usize,
+        readable_name: *mut c_char,
+        size2: usize,
+    ) -> c_int {
+        if num != 0 {
+            return 0;
+        }
+        if !short_name.is_null() {
+            copy_str(
+                unsafe { slice::from_raw_parts_mut(short_name, size1) },
+                "gl",
+            );
+        }
+        if !readable_name.is_null() {
+            copy_str(
+                unsafe { slice::from_raw_parts_mut(readable_name, size2) },
+                "OpenGL",
+            );
+        }
+        1
+    }
+
+    pub unsafe extern "C" fn double_time() -> f64 {
+        time() as f64
+    }
+
+    fn address_index(addr: *const c_void) -> usize {
+        unsafe { (addr as *const u32).read_unaligned() as usize }
+    }
+
+    pub unsafe extern "C" fn adr_to_string(addr: netadr_s) -> *const c_char {
+        let index = address_index(&addr as *const netadr_s as *const c_void);
+        with(|engine| match engine.addresses.get(index) {
+            Some(s) => s.as_ptr(),
+            None => c"<invalid address>".as_ptr(),
+        })
+    }
+
+    pub unsafe extern "C" fn compare_adr(a: *const c_void, b: *const c_void) -> c_int {
+        (address_index(a) == address_index(b)) as c_int
+    }
+
+    pub unsafe extern "C" fn net_adr_to_string(addr: *mut netadr_s) -> *const c_char {
+        unsafe { adr_to_string(*addr) }
+    }
+
+    pub unsafe extern "C" fn net_compare_adr(a: *mut netadr_s, b: *mut netadr_s) -> c_int {
+        unsafe { compare_adr(a as *const c_void, b as *const c_void) }
+    }
+
+    pub unsafe extern "C" fn net_string_to_adr(s: *mut c_char, addr: *mut netadr_s) -> c_int {
+        let s = unsafe { c_str(s) };
+        if s.is_empty() {
+            return 0;
+        }
+        let index = with(|engine| engine.address(s));
+        unsafe {
+            *addr = mem::zeroed();
+            (addr as *mut u32).write_unaligned(index);
+        }
+        1
+    }
+
+    pub unsafe extern "C" fn get_game_info(version: c_int) -> *mut gameinfo2_s {
+        unsafe {
+            let info = &mut *ptr::addr_of_mut!(GAME_INFO);
+            info.gi_version = version;
+            info
+        }
+    }
+
+    pub unsafe extern "C" fn get_mod_info(_version: c_int, _index: c_int) -> *mut gameinfo2_s {
+        ptr::null_mut()
+    }
+
+    pub unsafe extern "C" fn is_cvar_read_only(_name: *const c_char) -> c_int {
+        0
+    }
+}
+
+fn fill_engine_funcs(f: &mut ui_enginefuncs_s) {
+    use funcs::*;
+
+    f.pfnPIC_Load = Some(pic_load);
+    f.pfnPIC_Free = Some(pic_free);
+    f.pfnPIC_Width = Some(pic_width);
+    f.pfnPIC_Height = Some(pic_height);
+    f.pfnPIC_Set = Some(pic_set);
+    f.pfnPIC_Draw = Some(pic_draw);
+    f.pfnPIC_DrawHoles = Some(pic_draw);
+    f.pfnPIC_DrawTrans = Some(pic_draw);
+    f.pfnPIC_DrawAdditive = Some(pic_draw);
+    f.pfnPIC_EnableScissor = Some(enable_scissor);
+    f.pfnPIC_DisableScissor = Some(disable_scissor);
+    f.pfnFillRGBA = Some(fill_rgba);
+    f.pfnRegisterVariable = Some(register_variable);
+    f.pfnGetCvarFloat = Some(get_cvar_float);
+    f.pfnGetCvarString = Some(get_cvar_string);
+    f.pfnCvarSetString = Some(cvar_set_string);
+    f.pfnCvarSetValue = Some(cvar_set_value);
+    f.pfnAddCommand = Some(add_command);
+    f.pfnClientCmd = Some(client_cmd);
+    f.pfnDelCommand = Some(del_command);
+    f.pfnCmdArgc = Some(cmd_argc);
+    f.pfnCmdArgv = Some(cmd_argv);
+    f.pfnPlayLocalSound = Some(play_local_sound);
+    f.pfnGetPlayerModel = Some(get_player_model);
+    f.pfnSetModel = Some(set_model);
+    f.pfnClearScene = Some(clear_scene);
+    f.pfnRenderScene = Some(render_scene);
+    f.CL_CreateVisibleEntity = Some(create_visible_entity);
+    f.pfnFileExists = Some(file_exists);
+    f.pfnGetGameDir = Some(get_game_dir);
+    f.pfnCreateMapsList = Some(create_maps_list);
+    f.pfnClientInGame = Some(client_in_game);
+    f.pfnClientJoin = Some(client_join);
+    f.COM_LoadFile = Some(load_file);
+    f.COM_FreeFile = Some(free_file);
+    f.pfnKeyClearStates = Some(key_clear_states);
+    f.pfnSetKeyDest = Some(set_key_dest);
+    f.pfnKeynumToString = Some(keynum_to_string);
+    f.pfnKeyGetBinding = Some(key_get_binding);
+    f.pfnKeySetBinding = Some(key_set_binding);
+    f.pfnKeyIsDown = Some(key_is_down);
+    f.pfnKeyGetState = Some(key_get_state);
+    f.pfnGetFilesList = Some(get_files_list);
+    f.pfnGetSaveComment = Some(get_save_comment);
+    f.pfnCheckGameDll = Some(check_game_dll);
+    f.pfnGetClipboardData = Some(get_clipboard_data);
+    f.pfnWriteServerConfig = Some(write_server_config);
+    f.pfnPlayBackgroundTrack = Some(play_background_track);
+    f.pfnHostEndGame = Some(host_end_game);
+    f.pfnRandomFloat = Some(random_float);
+    f.pfnRandomLong = Some(random_long);
+    f.pfnIsMapValid = Some(is_map_valid);
+    f.pfnProcessImage = Some(process_image);
+    f.pfnGetModeString = Some(get_mode_string);
+    f.COM_SaveFile = Some(save_file);
+    f.COM_RemoveFile = Some(remove_file);
+}
+
+fn fill_extended_funcs(f: &mut ui_extendedfuncs_s) {
+    use funcs::*;
+
+    f.pfnEnableTextInput = Some(enable_text_input);
+    f.pfnGetRenderers = Some(get_renderers);
+    f.pfnDoubleTime = Some(double_time);
+    f.pfnAdrToString = Some(adr_to_string);
+    f.pfnCompareAdr = Some(compare_adr);
+    f.pfnGetGameInfo = Some(get_game_info);
+    f.pfnGetModInfo = Some(get_mod_info);
+    f.pfnIsCvarReadOnly = Some(is_cvar_read_only);
+}
+
+fn fill_net_api(f: &mut net_api_s) {
+    use funcs::*;
+
+    f.AdrToString = Some(net_adr_to_string);
+    f.CompareAdr = Some(net_compare_adr);
+    f.StringToAdr = Some(net_string_to_adr);
+}
@@ -60,6 +60,7 @@ define_strings! {
         save {
             TITLE_LOAD = "#GameUI_GameMenu_LoadGame",
             TITLE_SAVE = "#GameUI_GameMenu_SaveGame",
+            SAVE_COUNT = "{count} {count|save|saves}",
 
             DELETE_SAVE = "Delete save",
             NEW_SAVE = "New saved game",
@@ -76,6 +77,7 @@ define_strings! {
             // title
             TITLE_INTERNET = "Internet servers",
             TITLE_LOCAL = "Local servers",
+            SERVER_COUNT = "{count} {count|server|servers}",
 
             // menu
             JOIN_GAME = "Join game",
@@ -213,6 +215,9 @@ define_strings! {
         config_game {
             TITLE = "Game settings",
 
+            // config list
+            LANGUAGE = "Language",
+
             // config list (cstrike)
             WEAPON_LAG = "Weapon lag",
         }
@@ -280,6 +285,14 @@ define_strings! {
             WATER_RIPPLES = "Water ripples",
             OVERBRIGHTS = "Overbrights",
             TEXTURE_FILTERING = "Texture filtering",
+            THEME = "Theme",
+            BACKGROUND_ALPHA = "In-game background opacity",
+            FONT = "Font",
+            FONT_DEFAULT = "Default",
+            FONT_SIZE = "Font size",
+            SCALE = "Scale",
+            SCALE_AUTO = "Auto",
+            FONT_PREVIEW = "The quick brown fox jumps over the lazy dog 0123456789",
         }
         config_network {
             TITLE = "Network settings",
@@ -3,7 +3,7 @@
 #[macro_use]
 extern crate alloc;
 
-#[cfg(feature = "std")]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
 #[macro_use]
@@ -14,21 +14,38 @@ macro_rules! define_strings {
         $(pub mod $name {
             define_strings!($($body)*);
         })*
+
+        /// Calls `f` for every string declared in this module and submodules.
+        pub fn for_each(f: &mut dyn FnMut(&'static str)) {
+            $($name::for_each(f);)*
+        }
     };
     ($($name:ident = $value:expr),* $(,)?) => {
         $(pub const $name: &str = $value;)*
+
+        /// Calls `f` for every string declared in this module.
+        pub fn for_each(f: &mut dyn FnMut(&'static str)) {
+            $(f($name);)*
+        }
     };
 }
 
 mod config_list;
 mod export;
+#[cfg(any(test, feature = "preview"))]
+#[cfg_attr(not(test), allow(dead_code))]
+mod fake;
 mod i18n;
 mod input;
 mod macros;
 mod menu;
 mod prelude;
+#[cfg(feature = "preview")]
+pub mod preview;
 mod saved_servers;
 mod server_info;
 mod strings;
+#[cfg(test)]
+mod tests;
 mod ui;
 mod widgets;
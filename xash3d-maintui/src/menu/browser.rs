@@ -4,6 +4,7 @@ use core::{
     time::Duration,
 };
 
+use alloc::boxed::Box;
 use compact_str::ToCompactString;
 use ratatui::{
     prelude::*,
@@ -20,7 +21,7 @@ use crate::{
     saved_servers::{SavedServer, SavedServers},
     server_info::ServerInfo,
     strings::{self, Localize},
-    ui::{Control, Menu, Screen, State, utils},
+    ui::{Control, Menu, Screen, State, bidi, theme::theme, utils},
     widgets::{InputPopup, InputResult, List, ListPopup, MyTable, SelectResult, WidgetMut},
 };
 
@@ -493,12 +494,12 @@ impl Browser {
             let mut style = Style::default().white().on_dark_gray();
             if matches!(self.state.focus(), Focus::Tabs) {
                 if *tab == self.tab {
-                    style = style.black().on_yellow();
+                    style = theme().highlight;
                 } else if area.contains(screen.cursor) {
-                    style = style.black().on_green();
+                    style = theme().grabbed;
                 }
             } else if *tab == self.tab {
-                style = style.black().on_green();
+                style = theme().grabbed;
             }
             Line::raw(tab.as_str().localize())
                 .style(style)
@@ -668,6 +669,14 @@ impl Drop for Browser {
 }
 
 impl Menu for Browser {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new(self.is_lan);
+        menu.tab = self.tab;
+        menu.sort_by = self.sort_by;
+        menu.sort_reverse = self.sort_reverse;
+        Some(Box::new(menu))
+    }
+
     fn active(&mut self) {
         self.query_servers();
     }
@@ -687,9 +696,10 @@ impl Menu for Browser {
         } else {
             i18n::TITLE_INTERNET
         };
+        let count = strings::format(i18n::SERVER_COUNT, &[("count", self.table.len().into())]);
         let [menu_area, table_area] =
             Layout::horizontal([Constraint::Length(24), Constraint::Percentage(100)])
-                .areas(utils::main_block(title, area, buf));
+                .areas(utils::main_block_with_status(title, &count, area, buf));
 
         self.draw_menu(menu_area, buf, screen);
         if !self.is_lan {
@@ -840,8 +850,7 @@ impl Menu for Browser {
 }
 
 fn colorize(s: &str) -> Line<'_> {
-    let mut line = Line::default();
-    for (color, text) in xash3d::color::ColorIter::new(s) {
+    let spans = xash3d::color::ColorIter::new(s).map(|(color, text)| {
         let style = XashColor::try_from(color)
             .map(|color| {
                 let color = match color {
@@ -857,6 +866,14 @@ fn colorize(s: &str) -> Line<'_> {
                 Style::new().fg(color)
             })
             .unwrap_or_default();
+        (style, text)
+    });
+    if bidi::has_rtl(s) {
+        // spans are reordered with their colors
+        return bidi::styled_line(spans);
+    }
+    let mut line = Line::default();
+    for (style, text) in spans {
         line.push_span(Span::from(text).style(style))
     }
     line
@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use compact_str::{CompactString, ToCompactString};
 use ratatui::{
     prelude::*,
@@ -145,6 +146,12 @@ impl ChangeGame {
 }
 
 impl Menu for ChangeGame {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.table.state.select(self.table.state.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         let inner_area = utils::main_block(i18n::TITLE, area, buf);
         let [menu_area, table_area] = Layout::vertical([
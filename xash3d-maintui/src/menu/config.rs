@@ -9,6 +9,7 @@ mod touch_buttons;
 mod video;
 mod voice;
 
+use alloc::boxed::Box;
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
 
@@ -97,6 +98,12 @@ impl ConfigMenu {
 }
 
 impl Menu for ConfigMenu {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.menu.state.select(self.menu.state.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         let area = utils::menu_block(i18n::TITLE, area, buf);
         let len = self.menu.len();
@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
 
@@ -32,6 +33,12 @@ impl AudioConfig {
 }
 
 impl Menu for AudioConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.list.draw_centered(area, buf, screen);
     }
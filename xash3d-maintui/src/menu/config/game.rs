@@ -1,11 +1,13 @@
+use alloc::{boxed::Box, vec::Vec};
+use compact_str::CompactString;
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
 
 use crate::{
-    config_list::ConfigList,
+    config_list::{ConfigBackend, ConfigEntry, ConfigList},
     input::KeyEvent,
     prelude::*,
-    strings::Localize,
+    strings::{self, Localize},
     ui::{Control, Menu, Screen},
 };
 
@@ -13,6 +15,27 @@ mod i18n {
     pub use crate::i18n::menu::config_game::*;
 }
 
+struct Language {
+    names: Vec<CompactString>,
+}
+
+impl ConfigBackend<usize> for Language {
+    fn read(&self) -> Option<usize> {
+        let current = engine()
+            .get_cvar_string(strings::UI_LANGUAGE)
+            .to_str()
+            .ok()?;
+        self.names.iter().position(|i| i == current)
+    }
+
+    fn write(&mut self, value: usize) {
+        if let Some(name) = self.names.get(value) {
+            // strings and menus are reloaded on the next frame
+            strings::set_language(name);
+        }
+    }
+}
+
 pub struct GameConfig {
     list: ConfigList,
 }
@@ -20,6 +43,10 @@ pub struct GameConfig {
 impl GameConfig {
     pub fn new() -> Self {
         let mut list = ConfigList::with_back(i18n::TITLE.localize());
+        list.add({
+            let names = strings::languages();
+            ConfigEntry::list(i18n::LANGUAGE.localize(), names.clone()).build(Language { names })
+        });
 
         let engine = engine();
         let info = engine.game_info2().unwrap();
@@ -32,6 +59,12 @@ impl GameConfig {
 }
 
 impl Menu for GameConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.list.draw_centered(area, buf, screen);
     }
@@ -3,7 +3,7 @@ use core::{
     ffi::CStr,
 };
 
-use alloc::rc::Rc;
+use alloc::{boxed::Box, rc::Rc};
 use csz::CStrArray;
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
@@ -293,6 +293,12 @@ impl GamepadConfig {
 }
 
 impl Menu for GamepadConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.list.draw_centered(area, buf, screen);
     }
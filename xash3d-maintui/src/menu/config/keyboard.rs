@@ -3,6 +3,7 @@ use core::{
     str,
 };
 
+use alloc::boxed::Box;
 use compact_str::{CompactString, ToCompactString};
 use csz::CStrArray;
 use ratatui::{
@@ -240,7 +241,7 @@ impl Controls {
         let text = i18n::PRESS_KEY.localize();
         let line = Paragraph::new(text)
             .block(block)
-            .style(Style::new().black().bold().on_gray());
+            .style(utils::popup_block_style().bold());
         let width = 2 + text.len() as u16;
         let area = utils::centered_rect_fixed(width, 3, area);
         line.render(area, buf);
@@ -248,6 +249,10 @@ impl Controls {
 }
 
 impl Menu for Controls {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        Some(Box::new(Self::new()))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         let title = i18n::TITLE;
         let [menu_area, table_area] = Layout::vertical([
@@ -5,11 +5,11 @@ use core::{
     mem,
 };
 
-use alloc::{ffi::CString, rc::Rc, vec::Vec};
+use alloc::{boxed::Box, ffi::CString, rc::Rc, vec::Vec};
 use compact_str::{CompactString, ToCompactString};
 use csz::{CStrArray, CStrThin};
 use ratatui::prelude::*;
-use xash3d_ratatui::XashBackend;
+use xash3d_ratatui::{DrawCommand, XashBackend};
 use xash3d_ui::{
     color::RGBA,
     entity::EntityType,
@@ -441,31 +441,47 @@ impl Model {
         let px_area = screen.area_to_pixels(area);
         let pos = UiPoint::new(px_area.x.into(), px_area.y.into());
         let size = UiSize::new(px_area.width.into(), px_area.height.into());
-        engine.fill_rgba(RGBA::BLACK, UiRect::from((pos, size)));
-
-        let ent = engine.get_player_model_raw();
-        if let Some(ent) = unsafe { ent.as_mut() } {
-            // reset body, so it will be changed by cl_himodels setting
-            ent.curstate.body = 0;
-
-            ent.curstate.rendermode = kRenderNormal as c_int;
-            ent.curstate.renderamt = 255;
-
-            let viewpass = ViewPass::builder()
-                .pos(pos.x, pos.y)
-                .build(size.width as i32, size.height as i32);
-            let x = 45.0 / (viewpass.fov_y() / 2.0).to_radians().tan();
-            ent.origin.x = x;
-            ent.curstate.origin.x = x;
-            engine.clear_scene();
-            engine.create_visible_entity_raw(ent, EntityType::Normal);
-            engine.render_scene(viewpass);
+        let fill = DrawCommand::Fill {
+            color: RGBA::BLACK,
+            area: UiRect::from((pos, size)),
+        };
+        if !engine.get_player_model_raw().is_null() {
+            let scene = DrawCommand::Custom(Box::new(move |engine: UiEngineRef| {
+                let ent = engine.get_player_model_raw();
+                let Some(ent) = (unsafe { ent.as_mut() }) else {
+                    return;
+                };
+                // reset body, so it will be changed by cl_himodels setting
+                ent.curstate.body = 0;
+
+                ent.curstate.rendermode = kRenderNormal as c_int;
+                ent.curstate.renderamt = 255;
+
+                let viewpass = ViewPass::builder()
+                    .pos(pos.x, pos.y)
+                    .build(size.width as i32, size.height as i32);
+                let x = 45.0 / (viewpass.fov_y() / 2.0).to_radians().tan();
+                ent.origin.x = x;
+                ent.curstate.origin.x = x;
+                engine.clear_scene();
+                engine.create_visible_entity_raw(ent, EntityType::Normal);
+                engine.render_scene(viewpass);
+            }));
+            for pos in area.positions() {
+                buf[pos].reset();
+            }
+            screen.push_layer(area, buf, vec![fill, scene]);
             return;
         }
 
         // just draw some text if no image or model
+        for pos in area.positions() {
+            buf[pos].reset();
+        }
+        screen.push_layer(area, buf, vec![fill]);
         let name = self.get_model_name().as_c_str().to_string_lossy();
-        Line::raw(name).render(area, buf);
+        // the text is drawn above the fill, keep the fill color behind it
+        Line::raw(name).bg(Color::Black).render(area, buf);
     }
 
     fn key_event(&self, _backend: &XashBackend, event: KeyEvent) {
@@ -608,6 +624,12 @@ impl MultiplayerConfig {
 }
 
 impl Menu for MultiplayerConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn vid_init(&mut self) {
         self.model.update_preview();
     }
@@ -1,5 +1,6 @@
 use core::ffi::CStr;
 
+use alloc::boxed::Box;
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
 
@@ -156,6 +157,12 @@ impl NetworkConfig {
 }
 
 impl Menu for NetworkConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.list.draw_centered(area, buf, screen);
     }
@@ -1,6 +1,6 @@
 use core::ffi::c_int;
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use compact_str::{CompactString, ToCompactString};
 use csz::CStrThin;
 use ratatui::prelude::*;
@@ -122,6 +122,10 @@ impl TouchButtonsConfig {
 }
 
 impl Menu for TouchButtonsConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        Some(Box::new(Self::new()))
+    }
+
     fn active(&mut self) {
         self.load_list();
     }
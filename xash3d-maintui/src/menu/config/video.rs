@@ -1,19 +1,23 @@
 use core::{
+    cell::Cell,
     ffi::{CStr, c_uint},
     fmt::Write,
 };
 
-use compact_str::{CompactString, ToCompactString};
+use alloc::{boxed::Box, rc::Rc, string::String, vec::Vec};
+use compact_str::{CompactString, ToCompactString, format_compact};
 use csz::{CStrArray, CStrThin};
 use ratatui::prelude::*;
-use xash3d_ratatui::XashBackend;
+use xash3d_ratatui::{
+    MAX_FONT_SIZE, MIN_FONT_SIZE, UI_BACKGROUND_ALPHA, UI_FONT, UI_FONT_SIZE, UI_SCALE, XashBackend,
+};
 
 use crate::{
     config_list::{CVarInvert, ConfigBackend, ConfigEntry, ConfigList},
     input::KeyEvent,
     prelude::*,
     strings::Localize,
-    ui::{Control, Menu, Screen},
+    ui::{Control, Menu, Screen, theme},
 };
 
 mod i18n {
@@ -21,6 +25,7 @@ mod i18n {
 }
 
 const FPS_VALUES: &[u16] = &[30, 60, 75, 120, 144, 244, 360, 480, 960];
+const SCALE_VALUES: &[f32] = &[0.75, 1.0, 1.25, 1.5, 2.0, 3.0];
 
 fn get_renderer_name(short: &CStrThin) -> Option<CompactString> {
     let engine = engine();
@@ -157,8 +162,95 @@ impl ConfigBackend<usize> for Renderer {
     }
 }
 
+struct Theme {
+    names: Vec<CompactString>,
+}
+
+impl ConfigBackend<usize> for Theme {
+    fn read(&self) -> Option<usize> {
+        let current = engine().get_cvar_string(theme::UI_THEME).to_str().ok()?;
+        self.names.iter().position(|i| i == current)
+    }
+
+    fn write(&mut self, value: usize) {
+        if let Some(name) = self.names.get(value) {
+            theme::set_name(name);
+        }
+    }
+}
+
+struct Font {
+    /// Font file names, an empty name is the embedded font.
+    names: Vec<String>,
+}
+
+impl ConfigBackend<usize> for Font {
+    fn read(&self) -> Option<usize> {
+        let current = engine().get_cvar_string(UI_FONT).to_str().ok()?;
+        Some(self.names.iter().position(|i| i == current).unwrap_or(0))
+    }
+
+    fn write(&mut self, value: usize) {
+        let Some(name) = self.names.get(value) else {
+            return;
+        };
+        let mut buf = CStrArray::<256>::new();
+        if buf.cursor().write_str(name).is_ok() {
+            engine().set_cvar_string(UI_FONT, &buf);
+        }
+    }
+}
+
+/// The size of the current font is shown if ui_font_size is zero.
+struct FontSize {
+    current: Rc<Cell<u16>>,
+}
+
+impl ConfigBackend<f32> for FontSize {
+    fn read(&self) -> Option<f32> {
+        let size = match engine().get_cvar_float(UI_FONT_SIZE) as u16 {
+            0 => self.current.get(),
+            size => size,
+        };
+        Some(size as f32)
+    }
+
+    fn write(&mut self, value: f32) {
+        engine().set_cvar_float(UI_FONT_SIZE, value.round());
+    }
+}
+
+/// The first item is the auto scale, others are [SCALE_VALUES].
+struct Scale;
+
+impl ConfigBackend<usize> for Scale {
+    fn read(&self) -> Option<usize> {
+        let scale = engine().get_cvar_string(UI_SCALE).to_str().ok()?;
+        let Ok(scale) = scale.parse::<f32>() else {
+            return Some(0);
+        };
+        let i = SCALE_VALUES.iter().position(|&i| i == scale)?;
+        Some(i + 1)
+    }
+
+    fn write(&mut self, value: usize) {
+        let engine = engine();
+        let mut buf = CStrArray::<32>::new();
+        let result = match value.checked_sub(1) {
+            Some(i) => write!(buf.cursor(), "{}", SCALE_VALUES[i]),
+            None => buf.cursor().write_str("auto"),
+        };
+        if result.is_ok() {
+            engine.set_cvar_string(UI_SCALE, &buf);
+            // the scale is ignored if the font size is set
+            engine.set_cvar_float(UI_FONT_SIZE, 0.0);
+        }
+    }
+}
+
 pub struct VideoConfig {
     list: ConfigList,
+    font_size: Rc<Cell<u16>>,
 }
 
 impl VideoConfig {
@@ -224,13 +316,51 @@ impl VideoConfig {
                 .label(i18n::TEXTURE_FILTERING.localize())
                 .build(CVarInvert::new(c"gl_texture_nearest")),
         );
+        list.add({
+            let names = theme::list();
+            ConfigEntry::list(i18n::THEME.localize(), names.clone()).build(Theme { names })
+        });
+        list.slider(i18n::BACKGROUND_ALPHA.localize(), UI_BACKGROUND_ALPHA);
+        list.add({
+            let mut names = vec![String::new()];
+            names.extend(xash3d_ratatui::font_names(engine()));
+            let items = [i18n::FONT_DEFAULT.localize()]
+                .into_iter()
+                .chain(names[1..].iter().map(|i| i.as_str()));
+            ConfigEntry::list(i18n::FONT.localize(), items).build(Font { names })
+        });
+        list.add({
+            let items = [i18n::SCALE_AUTO.localize().into()].into_iter().chain(
+                SCALE_VALUES
+                    .iter()
+                    .map(|i| format_compact!("{}%", i * 100.0)),
+            );
+            ConfigEntry::list(i18n::SCALE.localize(), items).build(Scale)
+        });
+        let font_size = Rc::new(Cell::new(0));
+        list.add(
+            ConfigEntry::slider(MIN_FONT_SIZE as f32, MAX_FONT_SIZE as f32, 1.0)
+                .label(i18n::FONT_SIZE.localize())
+                .build(FontSize {
+                    current: font_size.clone(),
+                }),
+        );
+        // fonts are applied immediately, show how they look with a sample text
+        list.label(i18n::FONT_PREVIEW.localize());
 
-        Self { list }
+        Self { list, font_size }
     }
 }
 
 impl Menu for VideoConfig {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
+        self.font_size.set(screen.font_size);
         self.list.draw_centered(area, buf, screen);
     }
 
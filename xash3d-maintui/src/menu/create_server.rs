@@ -5,7 +5,7 @@ use core::{
     str,
 };
 
-use alloc::{rc::Rc, string::String, vec::Vec};
+use alloc::{boxed::Box, rc::Rc, string::String, vec::Vec};
 use compact_str::{CompactString, ToCompactString};
 use csz::CStrArray;
 use ratatui::prelude::*;
@@ -297,6 +297,12 @@ impl CreateServerMenu {
 }
 
 impl Menu for CreateServerMenu {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        let mut menu = Self::new();
+        menu.list.select(self.list.selected());
+        Some(Box::new(menu))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.list.draw_centered(area, buf, screen);
     }
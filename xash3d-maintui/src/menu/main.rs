@@ -1,6 +1,6 @@
 use core::ffi::{CStr, c_int};
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use compact_str::{CompactString, ToCompactString};
 use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
@@ -10,7 +10,7 @@ use crate::{
     input::{Key, KeyEvent},
     menu::{self, define_menu_items},
     prelude::*,
-    strings::Localize,
+    strings::{self, Localize},
     ui::{Control, Menu, Screen, State, utils},
     widgets::{ConfirmPopup, ConfirmResult, List, ListPopup, SelectResult, WidgetMut},
 };
@@ -73,10 +73,8 @@ impl MainMenu {
         let info = engine.game_info2().unwrap();
         let has_demo = engine.is_map_valid(info.demo_map());
         let title = info.title().to_str().unwrap_or("<invalid utf8>");
-        let hint_hazard_course = i18n::HAZARD_COURSE_HINT
-            .localize()
-            .replace("{title}", title)
-            .into();
+        let hint_hazard_course =
+            strings::format(i18n::HAZARD_COURSE_HINT, &[("title", title.into())]);
         let has_skills = !info.flags().intersects(GameInfoFlags::NOSKILLS);
         let has_hazard_course =
             !info.train_map().is_empty() && !info.train_map().eq_ignore_case(info.start_map());
@@ -311,6 +309,10 @@ impl MainMenu {
 }
 
 impl Menu for MainMenu {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        Some(Box::new(Self::new()))
+    }
+
     fn on_menu_hide(&mut self) {
         self.state.reset();
     }
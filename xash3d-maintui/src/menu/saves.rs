@@ -1,6 +1,6 @@
 use core::{ffi::CStr, str};
 
-use alloc::ffi::CString;
+use alloc::{boxed::Box, ffi::CString};
 use compact_str::CompactString;
 use ratatui::{
     prelude::*,
@@ -16,7 +16,7 @@ use xash3d_ui::{
 use crate::{
     input::{Key, KeyEvent},
     prelude::*,
-    strings::Localize,
+    strings::{self, Localize},
     ui::{Control, Menu, Screen, State, sound, utils},
     widgets::{
         ConfirmPopup, ConfirmResult, Image, List, ListPopup, MyTable, SelectResult, WidgetMut,
@@ -272,6 +272,10 @@ impl SavesMenu {
 }
 
 impl Menu for SavesMenu {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        Some(Box::new(Self::new(self.is_save)))
+    }
+
     fn active(&mut self) {
         self.update_list();
     }
@@ -282,7 +286,10 @@ impl Menu for SavesMenu {
         } else {
             i18n::TITLE_LOAD
         };
-        let inner_area = utils::main_block(title, area, buf);
+        // do not count the new save item
+        let saves = self.table.len().saturating_sub(self.is_save as usize);
+        let count = strings::format(i18n::SAVE_COUNT, &[("count", saves.into())]);
+        let inner_area = utils::main_block_with_status(title, &count, area, buf);
         let [menu_area, table_area, preview_area] = Layout::vertical([
             Constraint::Length(self.menu.len() as u16 + 1),
             Constraint::Ratio(2, 3),
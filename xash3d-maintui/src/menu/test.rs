@@ -1,10 +1,11 @@
-use ratatui::prelude::*;
+use alloc::boxed::Box;
+use ratatui::{prelude::*, widgets::Paragraph};
 use xash3d_ratatui::XashBackend;
 
 use crate::{
     input::KeyEvent,
-    ui::{Control, Menu, Screen, State, utils},
-    widgets::{ConfirmResult, Input, List, WidgetMut},
+    ui::{Control, Menu, Screen, State, animation, theme::theme, utils},
+    widgets::{Canvas, CanvasMode, ConfirmResult, Input, List, WidgetMut},
 };
 
 const MENU_TEST_A: &str = "Test A";
@@ -25,6 +26,25 @@ pub struct TestMenu {
     state: State<Focus>,
     list: List,
     input: Input,
+    canvas: Canvas,
+}
+
+/// Color gradient with a crosshair.
+fn test_canvas() -> Canvas {
+    let (width, height) = (32, 16);
+    let mut canvas = Canvas::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let r = (x * 255 / (width - 1)) as u8;
+            let g = (y * 255 / (height - 1)) as u8;
+            canvas.set(x, y, Some([r, g, 128]));
+        }
+    }
+    canvas.fill_rect(1, 1, 4, 2, [0, 0, 0]);
+    let white = [255, 255, 255];
+    canvas.line((0, height / 2), (width - 1, height / 2), white);
+    canvas.line((width / 2, 0), (width / 2, height - 1), white);
+    canvas
 }
 
 impl TestMenu {
@@ -34,6 +54,7 @@ impl TestMenu {
             state: State::default(),
             list: List::new_first([MENU_TEST_A, MENU_TEST_B, MENU_TEST_C, MENU_RESET, MENU_BACK]),
             input: Input::new(),
+            canvas: test_canvas(),
         }
     }
 
@@ -47,6 +68,12 @@ impl TestMenu {
     }
 
     fn test_b(&mut self) -> Control {
+        let mode = match self.canvas.mode() {
+            CanvasMode::Native => CanvasMode::HalfBlock,
+            CanvasMode::HalfBlock => CanvasMode::Braille,
+            CanvasMode::Braille => CanvasMode::Native,
+        };
+        self.canvas.set_mode(mode);
         Control::None
     }
 
@@ -68,6 +95,10 @@ impl TestMenu {
 }
 
 impl Menu for TestMenu {
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        Some(Box::new(Self::new()))
+    }
+
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
         self.frames += 1;
         let title = format!("Test (frame {})", self.frames);
@@ -79,9 +110,32 @@ impl Menu for TestMenu {
         let menu_area = utils::main_block("Menu", menu_area, buf);
         self.list.render(menu_area, buf, screen);
 
+        let [test_area, atlas_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(test_area);
+
+        let atlas = screen.atlas_stats();
+        let atlas_area = utils::main_block("Glyph atlas", atlas_area, buf);
+        Paragraph::new(vec![
+            Line::raw(format!("pages: {}", atlas.pages)),
+            Line::raw(format!("glyphs: {}", atlas.glyphs)),
+            Line::raw(format!("used: {}/{} px", atlas.used, atlas.capacity)),
+            Line::raw(format!("hits: {}", atlas.hits)),
+            Line::raw(format!("misses: {}", atlas.misses)),
+            Line::raw(format!("evictions: {}", atlas.evictions)),
+            Line::raw(format!("uploads: {}", atlas.uploads)),
+        ])
+        .render(atlas_area, buf);
+
         let test_area = utils::main_block("Test", test_area, buf);
+        let [test_area, canvas_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(test_area);
+        let canvas_title = format!("Canvas ({:?})", self.canvas.mode());
+        let canvas_area = utils::main_block(&canvas_title, canvas_area, buf);
+        self.canvas.render(canvas_area, buf, screen);
+
         let style = if matches!(self.state.focus(), Focus::Input) {
-            Style::default().black().on_green()
+            animation::pulse(theme().grabbed, screen.time)
         } else {
             Style::default()
         };
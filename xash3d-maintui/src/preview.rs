@@ -0,0 +1,229 @@
+//! Run menus in a terminal with a stand-in engine.
+//!
+//! Useful to work on layouts without starting Xash3D. Files are loaded from an optional
+//! game directory, client commands are printed after exit.
+
+use core::{ffi::c_int, time::Duration};
+use std::{
+    boxed::Box,
+    io::{self, Stdout},
+    panic,
+    path::PathBuf,
+    println,
+    time::Instant,
+};
+
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    crossterm::{
+        cursor::Show,
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind,
+            KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    },
+};
+use xash3d_ui::consts::keys::*;
+
+use crate::{fake, prelude::*, ui::Ui};
+
+const FRAME_TIME: Duration = Duration::from_millis(16);
+
+/// Maps a shifted character to the key on US keyboard layout.
+fn unshift(c: char) -> Option<u8> {
+    const SHIFTED: &[u8] = b"~!@#$%^&*()_+{}|:\"<>?";
+    const KEYS: &[u8] = b"`1234567890-=[]\\;',./";
+    if c.is_ascii_uppercase() {
+        return Some(c.to_ascii_lowercase() as u8);
+    }
+    let i = SHIFTED.iter().position(|&i| i as char == c)?;
+    Some(KEYS[i])
+}
+
+struct Preview {
+    ui: Ui,
+    start: Instant,
+}
+
+impl Preview {
+    fn new(game_dir: Option<PathBuf>) -> Self {
+        fake::init();
+        fake::with(|engine| engine.game_dir = game_dir);
+        let mut ui = Ui::new(engine());
+        ui.vid_init();
+        ui.set_active_menu(true);
+        Self {
+            ui,
+            start: Instant::now(),
+        }
+    }
+
+    fn resize(&mut self, columns: u16, rows: u16) {
+        fake::resize_grid(&mut self.ui, columns, rows);
+    }
+
+    fn redraw(&mut self) {
+        if !self.ui.is_visible() {
+            // hidden by the console or a return to the game, nothing to show instead
+            self.ui.set_active_menu(true);
+        }
+        let time = self.start.elapsed().as_secs_f32();
+        fake::set_time(time);
+        self.ui.redraw(time);
+    }
+
+    fn key(&mut self, key: u8, down: bool) {
+        self.ui.key_event(key as c_int, down);
+    }
+
+    fn press(&mut self, key: u8, modifiers: KeyModifiers) {
+        let mut modifier_keys = [None; 3];
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            modifier_keys[0] = Some(K_CTRL);
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            modifier_keys[1] = Some(K_ALT);
+        }
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            modifier_keys[2] = Some(K_SHIFT);
+        }
+        for i in modifier_keys.iter().flatten() {
+            self.key(*i, true);
+        }
+        self.key(key, true);
+        self.key(key, false);
+        for i in modifier_keys.iter().flatten() {
+            self.key(*i, false);
+        }
+    }
+
+    fn key_event(&mut self, event: event::KeyEvent) {
+        if event.kind == KeyEventKind::Release {
+            return;
+        }
+        let mut modifiers = event.modifiers;
+        let key = match event.code {
+            KeyCode::Char(c) => match unshift(c) {
+                Some(key) => {
+                    modifiers |= KeyModifiers::SHIFT;
+                    key
+                }
+                None if c.is_ascii() => c as u8,
+                None => return,
+            },
+            KeyCode::Enter => K_ENTER,
+            KeyCode::Esc => K_ESCAPE,
+            KeyCode::Backspace => K_BACKSPACE,
+            KeyCode::Tab => K_TAB,
+            KeyCode::BackTab => {
+                modifiers |= KeyModifiers::SHIFT;
+                K_TAB
+            }
+            KeyCode::Delete => K_DEL,
+            KeyCode::Up => K_UPARROW,
+            KeyCode::Down => K_DOWNARROW,
+            KeyCode::Left => K_LEFTARROW,
+            KeyCode::Right => K_RIGHTARROW,
+            KeyCode::Home => K_HOME,
+            KeyCode::End => K_END,
+            KeyCode::PageUp => K_PGUP,
+            KeyCode::PageDown => K_PGDN,
+            _ => return,
+        };
+        self.press(key, modifiers);
+    }
+
+    fn mouse_event(&mut self, event: MouseEvent) {
+        let cell = self.ui.terminal().backend().cell_size_in_pixels();
+        let x = event.column as c_int * cell.width as c_int + cell.width as c_int / 2;
+        let y = event.row as c_int * cell.height as c_int + cell.height as c_int / 2;
+        self.ui.mouse_move(x, y);
+
+        let button = |button| match button {
+            MouseButton::Left => K_MOUSE1,
+            MouseButton::Right => K_MOUSE2,
+            MouseButton::Middle => K_MOUSE3,
+        };
+        match event.kind {
+            MouseEventKind::Down(b) => self.key(button(b), true),
+            MouseEventKind::Up(b) => self.key(button(b), false),
+            MouseEventKind::ScrollUp => self.press(K_MWHEELUP, event.modifiers),
+            MouseEventKind::ScrollDown => self.press(K_MWHEELDOWN, event.modifiers),
+            _ => {}
+        }
+    }
+
+    fn is_quit(&self) -> bool {
+        fake::with(|engine| engine.commands.iter().any(|i| i == "quit"))
+    }
+
+    fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+        let size = terminal.size()?;
+        self.resize(size.width, size.height);
+        while !self.is_quit() {
+            self.redraw();
+            terminal.draw(|frame| {
+                frame.buffer_mut().merge(self.ui.terminal().buffer());
+                if let Some(position) = self.ui.terminal().backend().text_cursor() {
+                    frame.set_cursor_position(position);
+                }
+            })?;
+
+            if !event::poll(FRAME_TIME)? {
+                continue;
+            }
+            match event::read()? {
+                Event::Key(event) => self.key_event(event),
+                Event::Mouse(event) => self.mouse_event(event),
+                Event::Resize(columns, rows) => self.resize(columns, rows),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )
+}
+
+/// Restores the terminal before the default hook prints the panic message.
+fn set_panic_hook() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        hook(info);
+    }));
+}
+
+/// Runs menus in the current terminal until the quit command is sent.
+///
+/// Files missing in the stand-in engine are loaded from `game_dir` if present.
+pub fn run(game_dir: Option<PathBuf>) -> io::Result<()> {
+    let mut preview = Preview::new(game_dir);
+
+    set_panic_hook();
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = preview.run(&mut terminal);
+
+    restore_terminal()?;
+
+    for command in fake::with(|engine| core::mem::take(&mut engine.commands)) {
+        println!("{command}");
+    }
+
+    result
+}
@@ -1,49 +1,77 @@
 use core::{char, ffi::CStr, fmt::Write, str};
 
-use alloc::{borrow::Cow, string::String};
-use compact_str::CompactString;
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use compact_str::{CompactString, ToCompactString};
 use csz::{CStrArray, CStrThin};
 use hashbrown::HashMap;
 use xash3d_ui::{
-    cell::SyncOnceCell,
     cvar::CVarFlags,
     parser::{TokenError, Tokens},
 };
 
-use crate::prelude::*;
+use crate::{
+    i18n,
+    prelude::*,
+    ui::utils::{self, Registry},
+};
+
+mod report;
+mod template;
+
+pub use self::template::{Arg, PluralRule};
 
 const DEFAULT_LANGUAGE: &str = "english";
 const CUSTOM_STRINGS_PATH: &CStr = c"gfx/shell/strings.lst";
 
-const UI_LANGUAGE: &CStr = c"ui_language";
+pub const UI_LANGUAGE: &CStr = c"ui_language";
+
+/// Menu strings renamed by numbered entries of `strings.lst`.
+///
+/// Indices are the main menu hints in the string table of the classic Xash3D menus.
+const STRINGS_LIST: &[(u32, &str)] = {
+    use i18n::menu::main::*;
+
+    &[
+        (187, RESUME_GAME_HINT),
+        (188, NEW_GAME_HINT),
+        (189, HAZARD_COURSE_HINT),
+        (190, OPTIONS_HINT),
+        (191, LOAD_GAME_HINT),
+        (193, QUIT_HINT),
+        (194, INTERNET_HINT),
+        (195, CHANGE_GAME_HINT),
+    ]
+};
+
+/// Prefixes of localization files in the `resource` directory, the game directory
+/// is checked last.
+const LANGUAGE_FILE_PREFIXES: [&CStr; 4] = [c"gameui", c"valve", c"mainui", c"maintui"];
 
 #[derive(Default)]
 pub struct Strings {
+    language: CompactString,
+    plural_rule: PluralRule,
     map: HashMap<CompactString, CompactString>,
 }
 
 impl Strings {
-    fn new() -> Self {
-        let mut strings = Self::default();
-        strings.load();
+    fn load(language: &str) -> Self {
+        let mut strings = Self {
+            language: language.into(),
+            plural_rule: PluralRule::for_language(language),
+            ..Self::default()
+        };
+        strings.load_language(DEFAULT_LANGUAGE);
+        if language != DEFAULT_LANGUAGE {
+            strings.load_language(language);
+        }
+        // the game renames menu items on purpose, keep them in every language
+        strings.load_custom_strings();
         strings
     }
 
-    fn load(&mut self) {
-        if let Ok(_file) = engine().load_file(CUSTOM_STRINGS_PATH) {
-            warn!("unimplemented {CUSTOM_STRINGS_PATH:?}");
-        }
-
-        let eng = engine();
-        eng.client_cmd_now(c"exec mainui.cfg\n");
-
-        self.load_language(DEFAULT_LANGUAGE);
-        let lang = eng.get_cvar_string(UI_LANGUAGE);
-        if let Ok(lang) = lang.to_str() {
-            if lang != DEFAULT_LANGUAGE {
-                self.load_language(lang);
-            }
-        }
+    pub fn language(&self) -> &str {
+        &self.language
     }
 
     fn load_language(&mut self, lang: &str) {
@@ -51,7 +79,7 @@ impl Strings {
         let engine = engine();
         let info = engine.game_info2().unwrap();
         let gamedir = info.game_dir();
-        for i in [c"gameui", c"valve", c"mainui", c"maintui"] {
+        for i in LANGUAGE_FILE_PREFIXES {
             if i != gamedir {
                 self.load_gamedir(i.into(), lang);
             }
@@ -78,6 +106,51 @@ impl Strings {
         }
     }
 
+    fn load_custom_strings(&mut self) {
+        let Ok(file) = engine().load_file(CUSTOM_STRINGS_PATH) else {
+            return;
+        };
+        trace!("load custom strings from {CUSTOM_STRINGS_PATH:?}");
+        let src = bytes_to_string(file.as_bytes());
+        if let Err(err) = self.parse_custom_strings(&src) {
+            error!("failed to parse {CUSTOM_STRINGS_PATH:?}, {err:?}");
+        }
+    }
+
+    /// Parses `strings.lst` with `<number> "string"` and `#Key "string"` entries.
+    ///
+    /// Numbered entries are mapped to menu strings with [STRINGS_LIST], entries without
+    /// a counterpart in these menus are skipped.
+    fn parse_custom_strings<'a>(&mut self, src: &'a str) -> Result<(), TokenError<'a>> {
+        let mut tokens = Tokens::new(src);
+        let mut skipped = 0;
+        while let Some(name) = tokens.next() {
+            let name = name?;
+            let value = escape_string(tokens.parse()?);
+            let key = if let Some(name) = name.strip_prefix('#') {
+                name
+            } else if let Ok(index) = name.parse::<u32>() {
+                match STRINGS_LIST.iter().find(|(i, _)| *i == index) {
+                    Some((_, key)) => key.strip_prefix('#').unwrap_or(key),
+                    None => {
+                        skipped += 1;
+                        continue;
+                    }
+                }
+            } else {
+                warn!("{CUSTOM_STRINGS_PATH:?}: invalid string name \"{name}\"");
+                continue;
+            };
+            if !value.is_empty() {
+                self.map.insert(key.into(), value.into());
+            }
+        }
+        if skipped != 0 {
+            trace!("{CUSTOM_STRINGS_PATH:?}: skip {skipped} numbered strings unused by menus");
+        }
+        Ok(())
+    }
+
     fn parse_resource_file<'a>(&mut self, src: &'a str) -> Result<(), TokenError<'a>> {
         let mut tokens = Tokens::new(src);
         tokens.expect("lang")?;
@@ -109,16 +182,93 @@ impl Strings {
     pub fn get<'a>(&'a self, s: &'a str) -> &'a str {
         self.try_get(s).unwrap_or(s)
     }
+
+    /// Localizes the string and replaces placeholders with arguments.
+    ///
+    /// See [template::format] for the syntax of placeholders.
+    pub fn format(&self, s: &str, args: &[(&str, Arg)]) -> CompactString {
+        template::format(self.get(s), self.plural_rule, args)
+    }
+}
+
+static STRINGS: Registry<Strings> = Registry::new();
+
+/// Loads languages again, the fake engine has new files in every test.
+#[cfg(test)]
+pub fn reset() {
+    STRINGS.reset();
+}
+
+fn select(language: &str) -> &'static Strings {
+    STRINGS.select(language, || Strings::load(language))
 }
 
-static STRINGS: SyncOnceCell<Strings> = unsafe { SyncOnceCell::new() };
+fn current_language() -> CompactString {
+    let lang = engine().get_cvar_string(UI_LANGUAGE);
+    lang.to_str().unwrap_or(DEFAULT_LANGUAGE).into()
+}
 
 pub fn init() {
     engine().register_variable(UI_LANGUAGE, DEFAULT_LANGUAGE, CVarFlags::ARCHIVE);
 }
 
 pub fn strings() -> &'static Strings {
-    STRINGS.get_or_init(Strings::new)
+    STRINGS.current().unwrap_or_else(|| {
+        // ui_language is saved in mainui.cfg
+        engine().client_cmd_now(c"exec mainui.cfg\n");
+        select(&current_language())
+    })
+}
+
+/// Reloads strings if the language was changed.
+///
+/// Returns `true` if strings were reloaded, menus must be recreated to use them.
+pub fn update() -> bool {
+    // load strings first, mainui.cfg could change the language
+    let current = strings();
+    let language = current_language();
+    if language == current.language() {
+        return false;
+    }
+    trace!("change language to \"{language}\"");
+    select(&language);
+    true
+}
+
+pub fn set_language(name: &str) {
+    let mut buf = CStrArray::<128>::new();
+    if buf.cursor().write_str(name).is_ok() {
+        engine().set_cvar_string(UI_LANGUAGE, &buf);
+    }
+}
+
+/// Returns languages with localization files in the game filesystem.
+pub fn languages() -> Vec<CompactString> {
+    let engine = engine();
+    let info = engine.game_info2().unwrap();
+    let gamedir = info.game_dir().to_compact_string();
+    let mut list: Vec<CompactString> = vec![DEFAULT_LANGUAGE.into()];
+    let files = engine.get_files_list(c"resource/*_*.txt", false);
+    for i in files.iter() {
+        let Some((prefix, lang)) = i
+            .to_str()
+            .ok()
+            .and_then(utils::file_stem)
+            .and_then(|i| i.split_once('_'))
+        else {
+            continue;
+        };
+        let is_language_file = prefix == gamedir
+            || LANGUAGE_FILE_PREFIXES
+                .iter()
+                .any(|i| i.to_bytes() == prefix.as_bytes());
+        // skip other files like maintui_theme_*.txt
+        if is_language_file && !lang.contains('_') && !list.iter().any(|i| i == lang) {
+            list.push(lang.to_compact_string());
+        }
+    }
+    list.sort();
+    list
 }
 
 // pub fn try_get(s: &str) -> Option<&str> {
@@ -129,6 +279,33 @@ pub fn get(s: &str) -> &str {
     strings().get(s)
 }
 
+pub fn format(s: &str, args: &[(&str, Arg)]) -> CompactString {
+    strings().format(s, args)
+}
+
+/// Returns the list of untranslated, unused and missing strings for the current language.
+pub fn report() -> String {
+    let loaded = strings();
+    let language = loaded.language();
+    let mut translation = Strings::default();
+    translation.load_language(language);
+    let mut maintui = Strings::default();
+    maintui.load_gamedir(c"maintui".into(), language);
+    let mut declared = Vec::new();
+    crate::i18n::for_each(&mut |i| declared.push(i));
+
+    let mut out = String::new();
+    let report = report::Report {
+        language,
+        declared: &declared,
+        loaded,
+        translation: &translation,
+        maintui: &maintui,
+    };
+    report.write(&mut out);
+    out
+}
+
 fn from_utf32_lossy(data: &[u8], be: bool) -> String {
     let mut buf = String::with_capacity(data.len() / 4);
     for chunk in data.chunks_exact(4) {
@@ -162,7 +339,7 @@ fn from_utf16_lossy(data: &[u8], be: bool) -> String {
     buf
 }
 
-fn bytes_to_string(data: &[u8]) -> Cow<'_, str> {
+pub fn bytes_to_string(data: &[u8]) -> Cow<'_, str> {
     match data {
         [0x00, 0x00, 0xfe, 0xff, ..] => from_utf32_lossy(&data[4..], true).into(),
         [0xfe, 0xff, 0x00, 0x00, ..] => from_utf32_lossy(&data[4..], false).into(),
@@ -202,3 +379,43 @@ impl Localize for str {
         strings().get(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: &str = r#"
+"lang"
+{
+"Language" "English"
+"Tokens"
+{
+"GameUI_GameMenu_NewGame"   "New game"
+"GameUI_Quit"               "Quit"
+}
+}
+"#;
+
+    const CUSTOM: &str = r##"
+// comment
+188 "Start"
+#GameUI_GameMenu_NewGame "Begin\nthe game"
+"#GameUI_Quit" "Exit"
+"##;
+
+    #[test]
+    fn custom_strings() {
+        let mut strings = Strings::default();
+        strings.parse_resource_file(ENGLISH).unwrap();
+        // translations are loaded before custom strings
+        let german = ENGLISH.replace("\"Quit\"", "\"Beenden\"");
+        strings.parse_resource_file(&german).unwrap();
+        assert_eq!(strings.get("#GameUI_Quit"), "Beenden");
+
+        strings.parse_custom_strings(CUSTOM).unwrap();
+        assert_eq!(strings.get(i18n::menu::main::NEW_GAME_HINT), "Start");
+        assert_eq!(strings.try_get("#StringsList_188"), None);
+        assert_eq!(strings.get("#GameUI_GameMenu_NewGame"), "Begin\nthe game");
+        assert_eq!(strings.get("#GameUI_Quit"), "Exit");
+    }
+}
@@ -0,0 +1,120 @@
+use core::fmt::Write;
+
+use alloc::{string::String, vec::Vec};
+use hashbrown::HashSet;
+
+use super::{DEFAULT_LANGUAGE, Strings};
+
+/// Strings to check in the report.
+pub struct Report<'a> {
+    pub language: &'a str,
+    /// Strings declared in menus.
+    pub declared: &'a [&'a str],
+    /// All loaded strings with fallbacks.
+    pub loaded: &'a Strings,
+    /// Strings from all files for the language.
+    pub translation: &'a Strings,
+    /// Strings from `resource/maintui_<language>.txt`.
+    pub maintui: &'a Strings,
+}
+
+fn write_section<'a>(out: &mut String, title: &str, keys: impl Iterator<Item = &'a str>) {
+    let keys: Vec<_> = keys.collect();
+    writeln!(out, "// {title} ({})", keys.len()).unwrap();
+    for key in keys {
+        let pad = 32_usize.saturating_sub(key.len());
+        writeln!(out, "\"{key}\"{:pad$}\"\"", ' ').unwrap();
+    }
+    out.push('\n');
+}
+
+impl Report<'_> {
+    /// Writes untranslated, unused and missing game strings in the resource file format.
+    pub fn write(&self, out: &mut String) {
+        let mut seen = HashSet::new();
+        let declared: Vec<_> = self
+            .declared
+            .iter()
+            .copied()
+            .filter(|i| !i.is_empty() && seen.insert(*i))
+            .collect();
+
+        writeln!(
+            out,
+            "// maintui translation report for \"{}\"",
+            self.language
+        )
+        .unwrap();
+        out.push('\n');
+
+        if self.language != DEFAULT_LANGUAGE {
+            let untranslated = declared
+                .iter()
+                .copied()
+                .filter(|i| !i.starts_with('#') && self.translation.try_get(i).is_none());
+            write_section(out, "untranslated", untranslated);
+        }
+
+        let mut unused: Vec<_> = self
+            .maintui
+            .map
+            .keys()
+            .map(|i| i.as_str())
+            .filter(|i| {
+                !declared
+                    .iter()
+                    .any(|j| j.strip_prefix('#').unwrap_or(j) == *i)
+            })
+            .collect();
+        unused.sort_unstable();
+        write_section(out, "unused", unused.into_iter());
+
+        // shown as raw tokens in menus
+        let missing = declared
+            .iter()
+            .copied()
+            .filter(|i| i.starts_with('#') && self.loaded.try_get(i).is_none());
+        write_section(out, "missing game strings", missing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(tokens: &str) -> Strings {
+        let src = format!("\"lang\" {{ \"Language\" \"Test\" \"Tokens\" {{ {tokens} }} }}");
+        let mut strings = Strings::default();
+        strings.parse_resource_file(&src).unwrap();
+        strings
+    }
+
+    #[test]
+    fn report() {
+        let loaded = strings(r#""GameUI_Cancel" "Cancel" "Back" "Назад""#);
+        let translation = strings(r#""Back" "Назад""#);
+        let maintui = strings(r#""Back" "Назад" "Removed" "Удалено""#);
+        let report = Report {
+            language: "russian",
+            declared: &["Back", "Yes", "Yes", "#GameUI_Cancel", "#GameUI_Quit"],
+            loaded: &loaded,
+            translation: &translation,
+            maintui: &maintui,
+        };
+        let mut out = String::new();
+        report.write(&mut out);
+        let lines: Vec<_> = out.lines().filter(|i| !i.is_empty()).collect();
+        assert_eq!(
+            lines,
+            [
+                "// maintui translation report for \"russian\"",
+                "// untranslated (1)",
+                "\"Yes\"                             \"\"",
+                "// unused (1)",
+                "\"Removed\"                         \"\"",
+                "// missing game strings (1)",
+                "\"#GameUI_Quit\"                    \"\"",
+            ]
+        );
+    }
+}
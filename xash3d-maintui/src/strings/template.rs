@@ -0,0 +1,213 @@
+use core::{
+    fmt::{self, Write},
+    iter,
+};
+
+use compact_str::CompactString;
+
+/// Value of a placeholder.
+#[derive(Copy, Clone, Debug)]
+pub enum Arg<'a> {
+    Str(&'a str),
+    Int(i64),
+    Uint(u64),
+}
+
+impl<'a> From<&'a str> for Arg<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::Str(value)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident($int:ty): $($ty:ty),*) => {
+        $(impl From<$ty> for Arg<'_> {
+            fn from(value: $ty) -> Self {
+                Self::$variant(value as $int)
+            }
+        })*
+    };
+}
+
+impl_from_int!(Int(i64): i8, i16, i32, i64, isize);
+impl_from_int!(Uint(u64): u8, u16, u32, u64, usize);
+
+impl fmt::Display for Arg<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Str(s) => s.fmt(fmt),
+            Self::Int(n) => n.fmt(fmt),
+            Self::Uint(n) => n.fmt(fmt),
+        }
+    }
+}
+
+/// Selects a plural form for a number.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum PluralRule {
+    /// One form for all numbers.
+    None,
+    /// Singular for 1, plural for others.
+    #[default]
+    One,
+    /// Singular for 0 and 1, plural for others.
+    ZeroOne,
+    /// Forms for 1, 21, 31..., for 2-4, 22-24... and for others.
+    Slavic,
+    /// Forms for 1, for 2-4, 22-24... and for others.
+    Polish,
+    /// Forms for 1, for 2-4 and for others.
+    Czech,
+}
+
+impl PluralRule {
+    pub fn for_language(language: &str) -> Self {
+        match language {
+            "schinese" | "tchinese" | "japanese" | "korean" | "koreana" | "thai" | "vietnamese"
+            | "indonesian" => Self::None,
+            "french" | "brazilian" => Self::ZeroOne,
+            "russian" | "ukrainian" | "belarusian" | "serbian" | "croatian" => Self::Slavic,
+            "polish" => Self::Polish,
+            "czech" | "slovak" => Self::Czech,
+            _ => Self::One,
+        }
+    }
+
+    /// Returns the index of the plural form for the number.
+    pub fn form(&self, n: u64) -> usize {
+        let few = (2..=4).contains(&(n % 10)) && !(12..=14).contains(&(n % 100));
+        match self {
+            Self::None => 0,
+            Self::One => (n != 1) as usize,
+            Self::ZeroOne => (n > 1) as usize,
+            Self::Slavic if n % 10 == 1 && n % 100 != 11 => 0,
+            Self::Polish if n == 1 => 0,
+            Self::Slavic | Self::Polish if few => 1,
+            Self::Slavic | Self::Polish => 2,
+            Self::Czech => match n {
+                1 => 0,
+                2..=4 => 1,
+                _ => 2,
+            },
+        }
+    }
+}
+
+/// Replaces placeholders in the template with arguments.
+///
+/// * `{name}` is replaced with the argument.
+/// * `{name|form|...}` is replaced with the plural form for the numeric argument,
+///   the last form is used if there are not enough forms for the rule.
+/// * `{{` and `}}` are replaced with `{` and `}`.
+///
+/// Unknown placeholders are left as is.
+pub fn format(template: &str, rule: PluralRule, args: &[(&str, Arg)]) -> CompactString {
+    let mut out = CompactString::default();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let brace = &rest[..1];
+        if let Some(tail) = rest[1..].strip_prefix(brace) {
+            // escaped brace
+            out.push_str(brace);
+            rest = tail;
+            continue;
+        }
+        let end = match brace {
+            "{" => rest.find('}'),
+            _ => None,
+        };
+        let Some(end) = end else {
+            out.push_str(brace);
+            rest = &rest[1..];
+            continue;
+        };
+        if !push_placeholder(&mut out, &rest[1..end], rule, args) {
+            out.push_str(&rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn push_placeholder(
+    out: &mut CompactString,
+    placeholder: &str,
+    rule: PluralRule,
+    args: &[(&str, Arg)],
+) -> bool {
+    let mut parts = placeholder.split('|');
+    let name = parts.next().unwrap_or_default();
+    let Some((_, arg)) = args.iter().find(|(i, _)| *i == name) else {
+        return false;
+    };
+    // plural forms depend only on the absolute value
+    let n = match *arg {
+        Arg::Str(_) => None,
+        Arg::Int(n) => Some(n.unsigned_abs()),
+        Arg::Uint(n) => Some(n),
+    };
+    match (n, parts.next()) {
+        (_, None) => {
+            write!(out, "{arg}").unwrap();
+        }
+        (Some(n), Some(first)) => {
+            let index = rule.form(n);
+            let form = iter::once(first).chain(parts).take(index + 1).last();
+            out.push_str(form.unwrap_or_default());
+        }
+        (None, Some(_)) => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders() {
+        let args = [("title", "Half-Life".into()), ("count", 3.into())];
+        let s = format("Learn how to play {title}.", PluralRule::One, &args);
+        assert_eq!(s, "Learn how to play Half-Life.");
+        let s = format("{count} of {{count}} {unknown} {", PluralRule::One, &args);
+        assert_eq!(s, "3 of {count} {unknown} {");
+    }
+
+    #[test]
+    fn plurals() {
+        let template = "{n} {n|server|servers}";
+        let english = |n: i64| format(template, PluralRule::One, &[("n", n.into())]);
+        assert_eq!(english(1), "1 server");
+        assert_eq!(english(0), "0 servers");
+        assert_eq!(english(5), "5 servers");
+
+        let template = "{n} {n|сервер|сервера|серверов}";
+        let russian = |n: i64| format(template, PluralRule::Slavic, &[("n", n.into())]);
+        assert_eq!(russian(1), "1 сервер");
+        assert_eq!(russian(3), "3 сервера");
+        assert_eq!(russian(5), "5 серверов");
+        assert_eq!(russian(11), "11 серверов");
+        assert_eq!(russian(21), "21 сервер");
+        assert_eq!(russian(22), "22 сервера");
+
+        // not enough forms for the rule
+        let s = format(template, PluralRule::Polish, &[("n", 3.into())]);
+        assert_eq!(s, "3 сервера");
+        let s = format("{n|one}", PluralRule::Slavic, &[("n", 5.into())]);
+        assert_eq!(s, "one");
+    }
+
+    #[test]
+    fn unsigned_args() {
+        let template = "{n} {n|server|servers}";
+        let s = format(template, PluralRule::One, &[("n", u64::MAX.into())]);
+        assert_eq!(s, "18446744073709551615 servers");
+        let s = format(template, PluralRule::One, &[("n", 1_usize.into())]);
+        assert_eq!(s, "1 server");
+        let s = format(template, PluralRule::One, &[("n", (-1_i32).into())]);
+        assert_eq!(s, "-1 server");
+    }
+}
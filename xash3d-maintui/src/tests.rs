@@ -0,0 +1,239 @@
+//! Headless test harness for menus.
+
+mod backend;
+mod navigation;
+mod snapshots;
+
+use core::ffi::{CStr, c_int};
+use std::{
+    ffi::CString,
+    string::String,
+    sync::{Mutex, MutexGuard},
+    vec::Vec,
+};
+
+use alloc::boxed::Box;
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+use xash3d_ratatui::XashBackend;
+use xash3d_ui::{consts::keys::*, engine::net::netadr_s};
+
+use crate::{
+    fake,
+    prelude::*,
+    strings,
+    ui::{Menu, Ui, theme},
+};
+
+/// Menus share the global engine state, run tests one by one.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Simulated time between frames.
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+pub struct Harness {
+    ui: Ui,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self::with_engine(|_| {})
+    }
+
+    /// Creates the harness with the fake engine prepared by `setup`.
+    pub fn with_engine(setup: impl FnOnce(&mut fake::Engine)) -> Self {
+        let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        fake::init();
+        strings::reset();
+        theme::reset();
+        fake::with(|engine| {
+            // positions of cells are checked right after menu switches
+            engine.set_cvar("ui_reduced_motion", "1");
+            setup(engine);
+        });
+        let mut ui = Ui::new(engine());
+        ui.vid_init();
+        ui.set_active_menu(true);
+        let mut harness = Self { ui, _lock: lock };
+        harness.redraw();
+        // ignore commands sent during initialization
+        harness.take_commands();
+        harness
+    }
+
+    /// Creates the harness and opens the menu on top of the main menu.
+    pub fn with_menu(menu: fn() -> Box<dyn Menu>) -> Self {
+        let mut harness = Self::new();
+        harness.ui.push_menu(menu());
+        harness.redraw();
+        harness
+    }
+
+    pub fn redraw(&mut self) {
+        let time = fake::time() + FRAME_TIME;
+        fake::set_time(time);
+        self.ui.redraw(time);
+    }
+
+    /// Changes the screen size in pixels and reinitializes the video.
+    pub fn resize(&mut self, width: c_int, height: c_int) {
+        fake::set_screen_size(width, height);
+        self.ui.vid_init();
+        self.redraw();
+    }
+
+    /// Renders menus to the buffer without drawing it.
+    pub fn render(&mut self) {
+        self.ui.render();
+    }
+
+    /// Changes the screen size to fit the grid of cells.
+    pub fn resize_grid(&mut self, columns: u16, rows: u16) {
+        if !fake::resize_grid(&mut self.ui, columns, rows) {
+            panic!("failed to resize the screen to {columns}x{rows} cells");
+        }
+        self.redraw();
+    }
+
+    /// Presses and releases a key and redraws the screen.
+    pub fn key(&mut self, key: u8) {
+        self.ui.key_event(key as c_int, true);
+        self.ui.key_event(key as c_int, false);
+        self.redraw();
+    }
+
+    /// Presses a key with the control modifier.
+    pub fn ctrl(&mut self, key: u8) {
+        self.ui.key_event(K_CTRL as c_int, true);
+        self.key(key);
+        self.ui.key_event(K_CTRL as c_int, false);
+    }
+
+    /// Types every byte of the string as a separate key press.
+    pub fn keys(&mut self, s: &str) {
+        for c in s.bytes() {
+            self.key(c);
+        }
+    }
+
+    /// Shows a toast and redraws the screen.
+    pub fn show_toast(&mut self, text: &str) {
+        self.ui.show_toast(text);
+        self.redraw();
+    }
+
+    /// Runs a console command registered by menus.
+    pub fn command(&mut self, args: &[&CStr]) {
+        fake::with(|engine| engine.args = args.iter().map(|&i| CString::from(i)).collect());
+        match args[0].to_str().unwrap() {
+            "maintui_dump" => self.ui.dump_screen(),
+            "maintui_i18n_report" => self.ui.i18n_report(),
+            name => panic!("unknown command {name}"),
+        }
+        self.redraw();
+    }
+
+    /// Moves the mouse pointer to the pixel position.
+    pub fn mouse_move(&mut self, x: c_int, y: c_int) {
+        self.ui.mouse_move(x, y);
+        self.redraw();
+    }
+
+    pub fn backend(&self) -> &XashBackend {
+        self.ui.terminal().backend()
+    }
+
+    /// Returns the cell size in pixels.
+    pub fn cell_size(&self) -> (c_int, c_int) {
+        let cell = self.backend().cell_size_in_pixels();
+        (cell.width as c_int, cell.height as c_int)
+    }
+
+    /// Returns the font size in pixels.
+    pub fn font_size(&self) -> u16 {
+        self.backend().get_font_size()
+    }
+
+    /// Clicks the left mouse button at the center of a cell.
+    pub fn click(&mut self, x: u16, y: u16) {
+        let (width, height) = self.cell_size();
+        let px = x as c_int * width + width / 2;
+        let py = y as c_int * height + height / 2;
+        self.ui.mouse_move(px, py);
+        self.key(K_MOUSE1);
+    }
+
+    pub fn add_server(&mut self, addr: &str, info: &str) {
+        let addr: netadr_s = engine().string_to_addr(addr).unwrap();
+        self.ui.add_server_to_list(addr, info);
+        self.redraw();
+    }
+
+    /// Returns the cell under the mouse pointer.
+    pub fn cursor_position(&self) -> (u16, u16) {
+        let cursor = self.ui.terminal().backend().cursor_position();
+        (cursor.x, cursor.y)
+    }
+
+    /// Returns the number of menus in the history.
+    pub fn depth(&self) -> usize {
+        self.ui.history_len()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.ui.is_visible()
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        self.ui.terminal().buffer()
+    }
+
+    /// Returns the rendered text line by line.
+    ///
+    /// Cells hidden by wide characters are skipped.
+    pub fn lines(&self) -> Vec<String> {
+        let buffer = self.buffer();
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                let mut line = String::new();
+                let mut x = area.left();
+                while x < area.right() {
+                    let symbol = buffer[(x, y)].symbol();
+                    line.push_str(symbol);
+                    x += symbol.width().max(1) as u16;
+                }
+                line
+            })
+            .collect()
+    }
+
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines().iter().any(|line| line.contains(text))
+    }
+
+    /// Returns the cell position of the first occurrence of the text.
+    pub fn find(&self, text: &str) -> Option<(u16, u16)> {
+        let buffer = self.buffer();
+        self.lines().iter().enumerate().find_map(|(y, line)| {
+            let offset = line.find(text)?;
+            let x = line[..offset].width();
+            Some((buffer.area.x + x as u16, buffer.area.y + y as u16))
+        })
+    }
+
+    /// Returns and clears client commands sent by menus.
+    pub fn take_commands(&mut self) -> Vec<String> {
+        fake::with(|engine| core::mem::take(&mut engine.commands))
+    }
+
+    /// Returns and clears sounds played by menus.
+    pub fn take_sounds(&mut self) -> Vec<String> {
+        fake::with(|engine| core::mem::take(&mut engine.sounds))
+    }
+
+    pub fn cvar(&self, name: &str) -> Option<String> {
+        fake::with(|engine| engine.cvar(name).map(String::from))
+    }
+}
@@ -0,0 +1,97 @@
+//! The backend driven by [ratatui::Terminal].
+
+use std::string::String;
+
+use ratatui::{
+    Terminal,
+    backend::{Backend, ClearType},
+    layout::{Position, Size},
+    widgets::Paragraph,
+};
+use xash3d_ratatui::XashBackend;
+
+use crate::{fake, prelude::*, tests::Harness};
+
+fn terminal() -> Terminal<XashBackend> {
+    let mut terminal = Terminal::new(XashBackend::new(engine())).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(Paragraph::new("hello\nworld"), frame.area()))
+        .unwrap();
+    terminal
+}
+
+fn line(backend: &XashBackend, y: u16) -> String {
+    let screen = backend.screen();
+    (0..5).map(|x| screen[(x, y)].symbol()).collect()
+}
+
+#[test]
+fn draw() {
+    let _h = Harness::new();
+    let mut terminal = terminal();
+    assert_eq!(line(terminal.backend(), 0), "hello");
+    assert_eq!(line(terminal.backend(), 1), "world");
+
+    // only changed cells are sent to the backend
+    terminal
+        .draw(|frame| frame.render_widget(Paragraph::new("help"), frame.area()))
+        .unwrap();
+    assert_eq!(line(terminal.backend(), 0), "help ");
+    assert_eq!(line(terminal.backend(), 1), "     ");
+}
+
+#[test]
+fn cursor_position() {
+    let _h = Harness::new();
+    let mut terminal = terminal();
+    terminal.set_cursor_position((3, 1)).unwrap();
+    assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(3, 1));
+    terminal.show_cursor().unwrap();
+    assert_eq!(terminal.backend().text_cursor(), Some(Position::new(3, 1)));
+    terminal.hide_cursor().unwrap();
+    assert_eq!(terminal.backend().text_cursor(), None);
+}
+
+#[test]
+fn clear_region() {
+    let _h = Harness::new();
+    let mut terminal = terminal();
+    let backend = terminal.backend_mut();
+    backend.set_cursor_position(Position::new(2, 0)).unwrap();
+    backend.clear_region(ClearType::UntilNewLine).unwrap();
+    assert_eq!(line(backend, 0), "he   ");
+    assert_eq!(line(backend, 1), "world");
+
+    backend.set_cursor_position(Position::new(2, 1)).unwrap();
+    backend.clear_region(ClearType::BeforeCursor).unwrap();
+    assert_eq!(line(backend, 0), "     ");
+    assert_eq!(line(backend, 1), "  rld");
+}
+
+#[test]
+fn window_size() {
+    let _h = Harness::new();
+    let mut terminal = terminal();
+    let size = terminal.backend().size();
+    let window = terminal.backend_mut().window_size().unwrap();
+    assert_eq!(window.columns_rows, size);
+    assert_eq!(
+        window.pixels,
+        Size::new(fake::SCREEN_WIDTH as u16, fake::SCREEN_HEIGHT as u16)
+    );
+}
+
+#[test]
+fn atlas_pages() {
+    let h = Harness::new();
+    let stats = h.backend().atlas_stats();
+    assert!(stats.pages > 0);
+    fake::with(|engine| {
+        // shelves are parts of page pictures
+        for i in 0..stats.pages {
+            assert!(engine.has_picture(&format!("#mainui/backend/atlas{i}.bmp")));
+        }
+        let next = format!("#mainui/backend/atlas{}.bmp", stats.pages);
+        assert!(!engine.has_picture(&next));
+    });
+}
@@ -0,0 +1,422 @@
+use core::ffi::c_int;
+use std::string::String;
+
+use ratatui::style::{Color, Modifier, Style};
+use xash3d_ui::consts::keys::*;
+
+use crate::{
+    fake, i18n, menu,
+    strings::Localize,
+    tests::Harness,
+    ui::{animation, theme::theme, utils},
+};
+
+#[test]
+fn main_menu() {
+    let h = Harness::new();
+    assert_eq!(h.depth(), 1);
+    assert!(h.contains("Half-Life"));
+    assert!(h.contains(i18n::menu::main::NEW_GAME.localize()));
+    assert!(h.contains(i18n::menu::main::QUIT.localize()));
+}
+
+#[test]
+fn new_game_with_skill() {
+    let mut h = Harness::new();
+    h.key(b'n');
+    assert!(h.contains(i18n::menu::main::DIFFICULTY.localize()));
+    // normal skill is selected by default
+    h.key(K_ENTER);
+    assert_eq!(h.take_commands(), ["newgame"]);
+    assert_eq!(h.cvar("skill").as_deref(), Some("2"));
+    assert_eq!(h.cvar("maxplayers").as_deref(), Some("1"));
+}
+
+#[test]
+fn cancel_skill_popup() {
+    let mut h = Harness::new();
+    h.key(b'n');
+    h.key(K_ESCAPE);
+    assert!(!h.contains(i18n::menu::main::DIFFICULTY.localize()));
+    assert!(h.take_commands().is_empty());
+    assert_eq!(h.depth(), 1);
+}
+
+#[test]
+fn options_and_back() {
+    let mut h = Harness::new();
+    h.key(b'o');
+    assert_eq!(h.depth(), 2);
+    assert!(h.contains(i18n::menu::config::TITLE.localize()));
+    h.key(K_ESCAPE);
+    assert_eq!(h.depth(), 1);
+    assert!(h.contains(i18n::menu::main::NEW_GAME.localize()));
+}
+
+#[test]
+fn select_with_mouse() {
+    let mut h = Harness::new();
+    let (x, y) = h.find(i18n::menu::main::OPTIONS.localize()).unwrap();
+    h.click(x, y);
+    assert_eq!(h.depth(), 2);
+}
+
+#[test]
+fn quit_popup() {
+    let mut h = Harness::new();
+    h.ctrl(b'q');
+    assert!(h.contains(i18n::all::QUIT_POPUP_BODY.localize()));
+    h.key(b'n');
+    assert!(!h.contains(i18n::all::QUIT_POPUP_BODY.localize()));
+    assert!(h.take_commands().is_empty());
+
+    h.ctrl(b'q');
+    h.key(b'y');
+    assert_eq!(h.take_commands(), ["quit"]);
+}
+
+#[test]
+fn escape_returns_to_game() {
+    let mut h = Harness::with_engine(|engine| engine.in_game = true);
+    assert!(h.is_visible());
+    h.key(K_ESCAPE);
+    assert!(!h.is_visible());
+}
+
+#[test]
+fn internet_servers() {
+    let mut h = Harness::new();
+    h.key(b'i');
+    assert_eq!(h.depth(), 2);
+    h.add_server(
+        "127.0.0.1:27015",
+        "\\p\\49\\host\\Test server\\map\\crossfire\\gamedir\\valve\\numcl\\3\\maxcl\\16",
+    );
+    assert!(h.contains("Test server"));
+    assert!(h.contains("crossfire"));
+}
+
+#[test]
+fn toggle_checkbox() {
+    let mut h = Harness::with_engine(|engine| engine.set_cvar("snd_mute_losefocus", "0"));
+    h.key(b'o');
+    h.key(b'a');
+    assert_eq!(h.depth(), 3);
+    let label = i18n::menu::config_audio::MUTE_INACTIVE.localize();
+    let (x, y) = h.find(label).unwrap();
+    h.click(x, y);
+    assert_eq!(h.cvar("snd_mute_losefocus").as_deref(), Some("1"));
+    h.click(x, y);
+    assert_eq!(h.cvar("snd_mute_losefocus").as_deref(), Some("0"));
+}
+
+#[test]
+fn select_with_mouse_hover() {
+    let mut h = Harness::new();
+    let (x, y) = h.find(i18n::menu::main::OPTIONS.localize()).unwrap();
+    let (width, height) = h.cell_size();
+    h.mouse_move(x as i32 * width + 1, y as i32 * height + 1);
+    h.key(K_ENTER);
+    assert_eq!(h.depth(), 2);
+    assert!(!h.take_sounds().is_empty());
+}
+
+#[test]
+fn console() {
+    let mut h = Harness::new();
+    assert!(fake::with(|engine| engine.registered.contains_key("fg")));
+    let key_dest = fake::with(|engine| engine.key_dest);
+    h.ctrl(b'z');
+    assert!(!h.is_visible());
+    assert_ne!(fake::with(|engine| engine.key_dest), key_dest);
+}
+
+#[test]
+fn hazard_course() {
+    let mut h = Harness::new();
+    h.key(b't');
+    assert_eq!(h.take_commands(), ["hazardcourse"]);
+}
+
+#[test]
+fn small_screen() {
+    let mut h = Harness::new();
+    h.resize(640, 480);
+    assert!(h.contains(i18n::menu::main::QUIT.localize()));
+    h.key(b'o');
+    assert_eq!(h.depth(), 2);
+}
+
+#[test]
+fn auto_scale() {
+    let mut h = Harness::new();
+    let mut sizes = [0; 2];
+    for (i, (width, height)) in [(1024, 768), (2560, 1440)].into_iter().enumerate() {
+        h.resize(width, height);
+        let area = h.buffer().area;
+        assert!(
+            (80..=160).contains(&area.width) && (25..=50).contains(&area.height),
+            "{width}x{height} has grid {}x{}",
+            area.width,
+            area.height
+        );
+        sizes[i] = h.font_size();
+    }
+    assert!(sizes[0] < sizes[1], "font sizes {sizes:?}");
+    // the automatic size is not saved
+    assert_eq!(h.cvar("ui_font_size").as_deref(), Some("0"));
+
+    fake::with(|engine| engine.set_cvar("ui_scale", "1"));
+    h.redraw();
+    assert_eq!(h.font_size(), 21);
+}
+
+#[test]
+fn menu_transition() {
+    let mut h = Harness::with_engine(|engine| engine.set_cvar("ui_reduced_motion", "0"));
+    h.key(b'o');
+    let moving = h.lines();
+    for _ in 0..10 {
+        h.redraw();
+    }
+    let settled = h.lines();
+    assert_ne!(moving, settled);
+    h.redraw();
+    assert_eq!(h.lines(), settled);
+}
+
+#[test]
+fn menu_transition_with_layers() {
+    let mut h = Harness::with_engine(|engine| engine.set_cvar("ui_reduced_motion", "0"));
+    h.key(b'o');
+    for _ in 0..10 {
+        h.redraw();
+    }
+    // multiplayer settings draw the player model in a layer
+    for key in [K_DOWNARROW, K_DOWNARROW, K_DOWNARROW, K_DOWNARROW, K_ENTER] {
+        h.key(key);
+    }
+    assert_eq!(h.depth(), 3);
+    let opened = h.buffer().clone();
+    for _ in 0..10 {
+        h.redraw();
+    }
+    assert_eq!(*h.buffer(), opened);
+}
+
+#[test]
+fn popup_animation() {
+    let mut h = Harness::with_engine(|engine| engine.set_cvar("ui_reduced_motion", "0"));
+    let body = i18n::all::QUIT_POPUP_BODY.localize();
+    let menu = h.lines();
+    h.ctrl(b'q');
+    // the open animation starts from nothing
+    assert_eq!(h.lines(), menu);
+    h.redraw();
+    assert_ne!(h.lines(), menu);
+    assert!(!h.contains(body));
+    for _ in 0..10 {
+        h.redraw();
+    }
+    assert!(h.contains(body));
+
+    h.key(b'n');
+    // the popup is visible while the close animation is running
+    assert_ne!(h.lines(), menu);
+    for _ in 0..10 {
+        h.redraw();
+    }
+    assert_eq!(h.lines(), menu);
+}
+
+#[test]
+fn toast_timeout() {
+    let mut h = Harness::new();
+    h.show_toast("toast.txt");
+    assert!(h.contains("toast.txt"));
+    fake::set_time(fake::time() + animation::TOAST_TIME - 0.1);
+    h.redraw();
+    assert!(h.contains("toast.txt"));
+    fake::set_time(fake::time() + 0.1);
+    h.redraw();
+    assert!(!h.contains("toast.txt"));
+}
+
+#[test]
+fn wide_characters() {
+    let mut h = Harness::with_menu(menu::internet);
+    h.add_server(
+        "127.0.0.1:27015",
+        "\\p\\49\\host\\测试服务器\\map\\crossfire\\gamedir\\valve\\numcl\\3\\maxcl\\16",
+    );
+    let (x, y) = h.find("测试服务器").unwrap();
+    assert_eq!(h.find("试"), Some((x + 2, y)));
+
+    // the right half of a wide character belongs to its cell
+    let (width, height) = h.cell_size();
+    h.mouse_move((x + 3) as c_int * width + 1, y as c_int * height + 1);
+    assert_eq!(h.cursor_position(), (x + 2, y));
+}
+
+#[test]
+fn theme_from_file() {
+    const THEME: &str = r##"
+"theme"
+{
+    "palette"
+    {
+        "background" "#102030"
+        "yellow" "#ffcc00"
+    }
+    "styles"
+    {
+        "border" "light-blue"
+        "highlight" "black on yellow bold"
+    }
+}
+"##;
+    let h = Harness::with_engine(|engine| {
+        engine.add_file("resource/maintui_theme_custom.txt", THEME);
+        engine.set_cvar("ui_theme", "custom");
+    });
+    let palette = h.ui.terminal().backend().palette();
+    assert_eq!(palette.bg, [0x10, 0x20, 0x30]);
+    assert_eq!(palette.colors[3], [0xff, 0xcc, 0x00]);
+    assert_eq!(
+        utils::main_block_border_style(),
+        Style::new().fg(Color::LightBlue)
+    );
+    let highlight = Style::new()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    assert_eq!(theme().highlight, highlight);
+    // not defined in the file
+    assert_eq!(theme().popup, Style::new().fg(Color::Black).bg(Color::Gray));
+}
+
+#[test]
+fn numbered_custom_strings() {
+    let h = Harness::with_engine(|engine| {
+        engine.add_file("gfx/shell/strings.lst", "188 \"Begin the adventure\"\n");
+    });
+    // the selected new game item shows the renamed hint
+    assert!(!h.contains(i18n::menu::main::NEW_GAME_HINT.localize()));
+    assert!(h.contains("Begin the adventure"));
+}
+
+#[test]
+fn change_language() {
+    const GERMAN: &str = r#"
+"lang"
+{
+"Language" "German"
+"Tokens"
+{
+"Keyboard"  "Tastatur"
+}
+}
+"#;
+    let mut h = Harness::with_menu(menu::config);
+    assert!(h.contains(i18n::menu::config::KEYBOARD.localize()));
+    fake::with(|engine| {
+        engine.add_file("resource/maintui_german.txt", GERMAN);
+        engine.set_cvar("ui_language", "german");
+    });
+    h.redraw();
+    // the menu is recreated with new strings
+    assert_eq!(h.depth(), 2);
+    assert!(h.contains("Tastatur"));
+
+    let (x, y) = h.find("Tastatur").unwrap();
+    h.click(x, y);
+    assert_eq!(h.depth(), 3);
+    h.ctrl(b'q');
+    fake::with(|engine| engine.set_cvar("ui_language", "english"));
+    h.redraw();
+    // submenus are kept, the quit popup is replaced and keys go to the menu
+    assert_eq!(h.depth(), 3);
+    assert!(!h.contains(i18n::all::QUIT_POPUP_BODY.localize()));
+    h.key(b'b');
+    assert_eq!(h.depth(), 2);
+}
+
+#[test]
+fn change_language_in_submenu() {
+    const GERMAN: &str = r#"
+"lang"
+{
+"Language" "German"
+"Tokens"
+{
+"Network"  "Netzwerk"
+"Network mode"  "Netzwerkmodus"
+}
+}
+"#;
+    let mut h = Harness::with_menu(menu::config);
+    let (x, y) = h.find(i18n::menu::config::NETWORK.localize()).unwrap();
+    h.click(x, y);
+    assert_eq!(h.depth(), 3);
+    assert!(h.contains(i18n::menu::config_network::NETWORK_MODE.localize()));
+    fake::with(|engine| {
+        engine.add_file("resource/maintui_german.txt", GERMAN);
+        engine.set_cvar("ui_language", "german");
+    });
+    h.redraw();
+    // every menu in the stack is recreated with new strings
+    assert_eq!(h.depth(), 3);
+    assert!(h.contains("Netzwerkmodus"));
+    h.key(b'b');
+    assert_eq!(h.depth(), 2);
+    assert!(h.contains("Netzwerk"));
+}
+
+#[test]
+fn dump_command() {
+    let mut h = Harness::new();
+    h.command(&[c"maintui_dump", c"html", c"out.html"]);
+    let out = fake::with(|engine| engine.files.get("out.html").cloned()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.contains("Half-Life"));
+    assert!(h.contains("out.html"));
+
+    // unknown formats are rejected
+    h.command(&[c"maintui_dump", c"png"]);
+    assert!(fake::with(|engine| !engine
+        .files
+        .contains_key("maintui_dump.png")));
+}
+
+#[test]
+fn dump_command_failed() {
+    let mut h = Harness::with_engine(|engine| engine.read_only = true);
+    h.command(&[c"maintui_dump"]);
+    assert!(fake::with(|engine| engine.files.is_empty()));
+    // the toast is not shown
+    assert!(!h.contains("maintui_dump.txt"));
+}
+
+#[test]
+fn i18n_report_command() {
+    let mut h = Harness::with_engine(|engine| {
+        engine.set_cvar("ui_language", "russian");
+        engine.add_file(
+            "resource/maintui_russian.txt",
+            r#""lang" { "Language" "Russian" "Tokens" { "Removed" "Удалено" } }"#,
+        );
+    });
+    h.command(&[c"maintui_i18n_report"]);
+    let path = "maintui_i18n_report_russian.txt";
+    let out = fake::with(|engine| engine.files.get(path).cloned()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("// maintui translation report for \"russian\""));
+    assert!(out.contains("// unused (1)\n\"Removed\""));
+    assert!(h.contains(path));
+
+    // file names must be valid UTF-8
+    let files = fake::with(|engine| engine.files.len());
+    h.command(&[c"maintui_i18n_report", c"\xff.txt"]);
+    assert_eq!(fake::with(|engine| engine.files.len()), files);
+}
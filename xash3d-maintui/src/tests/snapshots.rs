@@ -0,0 +1,245 @@
+//! Golden tests for menu layouts.
+//!
+//! Every menu is rendered at several grid sizes and compared with the files in
+//! `src/tests/snapshots/`. A missing snapshot is an error. Set
+//! `MAINTUI_UPDATE_SNAPSHOTS=1` to create missing snapshots or to overwrite existing
+//! snapshots after an intended layout change and review the diff before committing it.
+
+use core::fmt::Write;
+use std::{env, format, fs, path::PathBuf, string::String};
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Style},
+};
+use xash3d_ui::consts::keys::*;
+
+use crate::{menu, tests::Harness};
+
+const SIZES: &[(u16, u16)] = &[(40, 15), (80, 25), (200, 60)];
+
+const KEYBOARD_ACTIONS: &str = r#"
+"blank"         "Movement"
+"+forward"      "Move forward"
+"+back"         "Move back"
+"+jump"         "Jump"
+"blank"         "Combat"
+"+attack"       "Fire"
+"#;
+
+const MAPS: &str = r#"
+c1a0 "Anomalous Materials"
+crossfire "Crossfire"
+datacore "Datacore"
+"#;
+
+/// Serializes the buffer text followed by runs of styled cells.
+fn serialize(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            out.push_str(buffer[(x, y)].symbol());
+        }
+        out.push('\n');
+    }
+
+    out.push_str("--- styles\n");
+    let default = Cell::EMPTY.style();
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let style = buffer[(x, y)].style();
+            let start = x;
+            while x < area.right() && buffer[(x, y)].style() == style {
+                x += 1;
+            }
+            if style != default {
+                let Style {
+                    fg,
+                    bg,
+                    add_modifier,
+                    ..
+                } = style;
+                write!(out, "{y}:{start}..{x}").unwrap();
+                if let Some(fg) = fg.filter(|&i| i != Color::Reset) {
+                    write!(out, " fg={fg}").unwrap();
+                }
+                if let Some(bg) = bg.filter(|&i| i != Color::Reset) {
+                    write!(out, " bg={bg}").unwrap();
+                }
+                if !add_modifier.is_empty() {
+                    write!(out, " {add_modifier:?}").unwrap();
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let area = buffer.area;
+    let actual = serialize(buffer);
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "src/tests/snapshots",
+        &format!("{name}_{}x{}.txt", area.width, area.height),
+    ]
+    .iter()
+    .collect();
+
+    let update = env::var_os("MAINTUI_UPDATE_SNAPSHOTS").is_some_and(|i| i != "0");
+    if update {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        std::eprintln!("write snapshot {}", path.display());
+        return;
+    }
+    match fs::read_to_string(&path) {
+        Ok(expected) => {
+            if expected != actual {
+                let line = expected
+                    .lines()
+                    .zip(actual.lines())
+                    .position(|(a, b)| a != b)
+                    .unwrap_or(expected.lines().count().min(actual.lines().count()));
+                panic!(
+                    "snapshot {} does not match at line {}\n\
+                     expected:\n{expected}\n\
+                     actual:\n{actual}\n\
+                     set MAINTUI_UPDATE_SNAPSHOTS=1 to update snapshots",
+                    path.display(),
+                    line + 1,
+                );
+            }
+        }
+        Err(err) => {
+            panic!(
+                "failed to read snapshot {}: {err}\n\
+                 actual:\n{actual}\n\
+                 set MAINTUI_UPDATE_SNAPSHOTS=1 to create snapshots",
+                path.display(),
+            );
+        }
+    }
+}
+
+fn check_sizes(name: &str, mut open: impl FnMut() -> Harness) {
+    for &(columns, rows) in SIZES {
+        let mut h = open();
+        h.resize_grid(columns, rows);
+        h.render();
+        assert_snapshot(name, h.buffer());
+    }
+}
+
+fn open_config_page(keys: &[u8]) -> Harness {
+    let mut h = Harness::with_engine(|engine| {
+        engine.add_file("gfx/shell/kb_act.lst", KEYBOARD_ACTIONS);
+        engine.add_file("gfx/shell/kb_def.lst", "");
+    });
+    h.key(b'o');
+    for &key in keys {
+        h.key(key);
+    }
+    assert_eq!(h.depth(), 3, "failed to open config page with {keys:?}");
+    h
+}
+
+#[test]
+fn main_menu() {
+    check_sizes("main", Harness::new);
+}
+
+#[test]
+fn main_menu_popups() {
+    check_sizes("main_skill", || {
+        let mut h = Harness::new();
+        h.key(b'n');
+        h
+    });
+    check_sizes("main_quit", || {
+        let mut h = Harness::new();
+        h.ctrl(b'q');
+        h
+    });
+}
+
+#[test]
+fn load_menu() {
+    check_sizes("load", || Harness::with_menu(menu::load));
+}
+
+#[test]
+fn save_menu() {
+    check_sizes("save", || Harness::with_menu(menu::save));
+}
+
+#[test]
+fn internet_menu() {
+    check_sizes("internet", || {
+        let mut h = Harness::with_menu(menu::internet);
+        h.add_server(
+            "127.0.0.1:27015",
+            "\\p\\49\\host\\Test server\\map\\crossfire\\gamedir\\valve\\numcl\\3\\maxcl\\16",
+        );
+        h.add_server(
+            "127.0.0.2:27015",
+            "\\p\\49\\host\\Server with a very long name that does not fit\\map\\datacore\\gamedir\\valve\\numcl\\0\\maxcl\\32",
+        );
+        h
+    });
+}
+
+#[test]
+fn lan_menu() {
+    check_sizes("lan", || Harness::with_menu(menu::lan));
+}
+
+#[test]
+fn change_game_menu() {
+    check_sizes("change_game", || Harness::with_menu(menu::change_game));
+}
+
+#[test]
+fn create_server_menu() {
+    check_sizes("create_server", || {
+        let mut h = Harness::with_engine(|engine| engine.add_file("maps.lst", MAPS));
+        h.key(b'i');
+        h.key(b'c');
+        assert_eq!(h.depth(), 3);
+        h
+    });
+}
+
+#[test]
+fn config_menu() {
+    check_sizes("config", || Harness::with_menu(menu::config));
+}
+
+#[test]
+fn config_pages() {
+    check_sizes("config_keyboard", || open_config_page(b"e"));
+    check_sizes("config_mouse", || open_config_page(b"m"));
+    check_sizes("config_gamepad", || open_config_page(b"p"));
+    check_sizes("config_game", || open_config_page(b"g"));
+    check_sizes("config_audio", || open_config_page(b"a"));
+    check_sizes("config_video", || open_config_page(b"v"));
+    check_sizes("config_network", || open_config_page(b"n"));
+
+    // pages without bindings
+    check_sizes("config_multiplayer", || {
+        open_config_page(&[K_DOWNARROW, K_DOWNARROW, K_DOWNARROW, K_DOWNARROW, K_ENTER])
+    });
+    check_sizes("config_voice", || {
+        open_config_page(&[
+            K_DOWNARROW,
+            K_DOWNARROW,
+            K_DOWNARROW,
+            K_DOWNARROW,
+            K_DOWNARROW,
+            K_ENTER,
+        ])
+    });
+}
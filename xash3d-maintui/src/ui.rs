@@ -1,16 +1,21 @@
 mod screen;
 mod state;
 
+pub mod animation;
+pub mod bidi;
 pub mod sound;
 pub mod symbols;
+pub mod theme;
 pub mod utils;
 
-use core::ffi::c_int;
+use core::{ffi::c_int, mem};
 
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use compact_str::{CompactString, ToCompactString};
 use csz::CStrThin;
 use ratatui::prelude::*;
-use xash3d_ratatui::{XashBackend, XashTerminal};
+use unicode_width::UnicodeWidthStr;
+use xash3d_ratatui::{DumpFormat, XashBackend, XashTerminal};
 use xash3d_ui::{
     color::RGBA,
     engine::{ActiveMenu, net::netadr_s},
@@ -26,6 +31,8 @@ use crate::{
     widgets::{ConfirmPopup, ConfirmResult, WidgetMut},
 };
 
+use self::animation::Transition;
+
 pub use self::{screen::Screen, state::State};
 
 pub enum Control {
@@ -51,6 +58,12 @@ impl Control {
 pub trait Menu {
     fn vid_init(&mut self) {}
     fn active(&mut self) {}
+    /// Returns a new instance of the menu with strings for the current language.
+    ///
+    /// The menu is kept with old strings if it returns `None`.
+    fn recreate(&self) -> Option<Box<dyn Menu>> {
+        None
+    }
     fn on_menu_hide(&mut self) {}
     fn draw(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen);
     fn key_event(&mut self, backend: &XashBackend, event: KeyEvent) -> Control {
@@ -103,11 +116,16 @@ pub struct Ui {
     touch: Touch,
     emulated_wheel: Option<Position>,
     quit_popup: Option<ConfirmPopup>,
+    transition: Option<Transition>,
+    /// Short message and the time when it was shown.
+    toast: Option<(CompactString, f32)>,
 }
 
 impl Ui {
     pub fn new(engine: UiEngineRef) -> Self {
         strings::init();
+        theme::init();
+        animation::init();
 
         // TODO: helper macro
         unsafe extern "C" fn cmd_fg() {
@@ -117,6 +135,22 @@ impl Ui {
         }
         engine.add_command(c"fg", cmd_fg).unwrap();
 
+        unsafe extern "C" fn cmd_dump() {
+            unsafe { Dll::global_assume_init_ref() }
+                .ui_mut()
+                .dump_screen();
+        }
+        engine.add_command(c"maintui_dump", cmd_dump).unwrap();
+
+        unsafe extern "C" fn cmd_i18n_report() {
+            unsafe { Dll::global_assume_init_ref() }
+                .ui_mut()
+                .i18n_report();
+        }
+        engine
+            .add_command(c"maintui_i18n_report", cmd_i18n_report)
+            .unwrap();
+
         Self {
             engine,
             history: vec![],
@@ -129,6 +163,8 @@ impl Ui {
             touch: Touch::Stop,
             emulated_wheel: None,
             quit_popup: None,
+            transition: None,
+            toast: None,
         }
     }
 
@@ -143,6 +179,108 @@ impl Ui {
         true
     }
 
+    /// Returns the file path from the argument `n` of the console command or `default`
+    /// if the argument is missing.
+    ///
+    /// Returns `None` if the argument is not a valid file name.
+    fn command_path(&self, n: usize, default: impl FnOnce() -> String) -> Option<String> {
+        let engine = self.engine;
+        if engine.cmd_argc() <= n {
+            return Some(default());
+        }
+        let command = engine.cmd_argv(0);
+        let command = command.to_str().unwrap_or_default();
+        match engine.cmd_argv(n).to_str() {
+            Ok(path) if !path.is_empty() => Some(String::from(path)),
+            _ => {
+                error!("{command}: invalid file name");
+                None
+            }
+        }
+    }
+
+    /// Saves the output of the console command to a file in the game directory and shows
+    /// the path in a toast.
+    fn save_command_output(&mut self, path: &str, data: &[u8]) {
+        let engine = self.engine;
+        let command = engine.cmd_argv(0);
+        let command = command.to_str().unwrap_or_default();
+        if engine.save_file(path, data) {
+            info!("{command}: saved to \"{path}\"");
+            self.show_toast(path);
+        } else {
+            error!("{command}: failed to save \"{path}\"");
+        }
+    }
+
+    /// Saves the last rendered screen to a file in the game directory.
+    ///
+    /// Usage: `maintui_dump [text|ansi|html] [file]`.
+    pub fn dump_screen(&mut self) {
+        let engine = self.engine;
+        let format = if engine.cmd_argc() > 1 {
+            let arg = engine.cmd_argv(1);
+            let arg = arg.to_str().unwrap_or_default();
+            match arg.parse() {
+                Ok(format) => format,
+                Err(_) => {
+                    error!("maintui_dump: unknown format \"{arg}\", expected text, ansi or html");
+                    return;
+                }
+            }
+        } else {
+            DumpFormat::default()
+        };
+        let Some(path) = self.command_path(2, || format!("maintui_dump.{}", format.extension()))
+        else {
+            return;
+        };
+        let out = self.terminal.dump(format);
+        self.save_command_output(&path, out.as_bytes());
+    }
+
+    /// Saves the list of missing translations to a file in the game directory.
+    ///
+    /// Usage: `maintui_i18n_report [file]`.
+    pub fn i18n_report(&mut self) {
+        let default = || format!("maintui_i18n_report_{}.txt", strings::strings().language());
+        let Some(path) = self.command_path(1, default) else {
+            return;
+        };
+        let out = strings::report();
+        self.save_command_output(&path, out.as_bytes());
+    }
+
+    /// Shows a short message at the bottom of the screen for a few seconds.
+    pub fn show_toast(&mut self, text: &str) {
+        let time = self.terminal.backend().time();
+        self.toast = Some((text.to_compact_string(), time));
+    }
+
+    /// Recreates menus in the history to apply a new language.
+    fn recreate_menus(&mut self) {
+        let history = mem::take(&mut self.history);
+        self.history = history
+            .into_iter()
+            .map(|menu| menu.recreate().unwrap_or(menu))
+            .collect();
+        if self.history.is_empty() {
+            self.history.push(crate::menu::main());
+        }
+        if let Some(menu) = self.history.last_mut() {
+            menu.active();
+        }
+        // the new popup is closed and the menu stack could be changed
+        self.quit_popup = Some(create_quit_popup());
+        self.focus = Focus::Main;
+        self.transition = None;
+    }
+
+    fn start_transition(&mut self, direction: animation::Direction) {
+        let time = self.terminal.backend().time();
+        self.transition = Transition::new(time, direction);
+    }
+
     fn activate_console(&mut self, active: bool) {
         self.active = !active;
         if active {
@@ -214,6 +352,7 @@ impl Ui {
                 Control::Back => {
                     if self.back() {
                         sound::switch_menu();
+                        self.start_transition(animation::Direction::Backward);
                     }
                 }
                 Control::BackHide => {
@@ -222,6 +361,7 @@ impl Ui {
                 }
                 Control::BackMain => {
                     self.back_main();
+                    self.start_transition(animation::Direction::Backward);
                 }
                 Control::BackMainHide => {
                     self.back_main();
@@ -234,6 +374,7 @@ impl Ui {
                     menu.active();
                     self.history.push(menu);
                     sound::switch_menu();
+                    self.start_transition(animation::Direction::Forward);
                 }
                 Control::Console => self.activate_console(true),
                 Control::GrabInput(enabled) => self.grab_input = enabled,
@@ -313,34 +454,79 @@ impl Ui {
         }
     }
 
-    pub fn redraw(&mut self, _time: f32) {
+    pub fn redraw(&mut self, time: f32) {
         if !self.active {
             return;
         }
 
+        // ui_language could be changed from the console or settings
+        let language_changed = strings::update();
         if self.history.is_empty() {
             // XXX: init here bacause ui_language cvar needed for localization is not ready
             // in Ui::init() and Ui::vid_init()
             self.history.push(crate::menu::main());
-            self.quit_popup = Some(ConfirmPopup::with_title(
-                i18n::all::QUIT_POPUP_TITLE.localize(),
-                i18n::all::QUIT_POPUP_BODY.localize(),
-            ));
+            self.quit_popup = Some(create_quit_popup());
+        } else if language_changed {
+            self.recreate_menus();
+        }
+
+        if let Some(theme) = theme::update() {
+            self.terminal.backend_mut().set_palette(theme.palette);
+        }
+        // ui_font or ui_font_size could be changed from the console or settings
+        self.terminal.backend_mut().update_font();
+        let in_game = self.engine.client_in_game();
+        self.terminal.backend_mut().set_translucent(in_game);
+        self.terminal.backend_mut().set_time(time);
+        if self.transition.is_some_and(|i| i.is_finished(time)) {
+            self.transition = None;
+        }
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|(_, start)| time - start >= animation::TOAST_TIME)
+        {
+            self.toast = None;
         }
 
         self.terminal.backend_mut().draw_background();
-        if let Some(menu) = self.history.last_mut() {
-            self.terminal.draw(|area, buffer, backend| {
-                let screen = Screen::new(backend);
+        self.render();
+        self.terminal.flush();
+    }
 
-                menu.draw(area, buffer, &screen);
+    /// Renders menus to the terminal buffer without drawing it on the screen.
+    pub fn render(&mut self) {
+        let Some(menu) = self.history.last_mut() else {
+            return;
+        };
+        self.terminal.render(|area, buffer, backend| {
+            let screen = Screen::new(backend);
+
+            menu.draw(area, buffer, &screen);
+            // layers are drawn in pixels at fixed positions, do not move cells away from them
+            let transition = self.transition.filter(|_| !screen.has_layers());
+            if let Some(transition) = transition {
+                transition.apply(buffer, screen.time);
+            }
 
-                if self.focus == Focus::QuitPopup {
-                    let popup = self.quit_popup.as_mut().unwrap();
-                    popup.render(area, buffer, &screen);
-                }
+            let focus = self.focus;
+            let quit_popup = self.quit_popup.as_mut().filter(|popup| {
+                // keep the popup visible while the close animation is running
+                focus == Focus::QuitPopup || popup.is_closing(screen.time)
             });
-        }
+            if let Some(popup) = quit_popup {
+                popup.render(area, buffer, &screen);
+            }
+
+            if let Some((text, _)) = &self.toast {
+                render_toast(text, area, buffer);
+            }
+
+            let text_cursor = screen.text_cursor();
+            let layers = screen.into_layers();
+            backend.set_text_cursor(text_cursor);
+            backend.set_layers(layers);
+        });
     }
 
     pub fn key_event(&mut self, key: c_int, down: bool) {
@@ -420,7 +606,7 @@ impl Ui {
     pub fn mouse_move(&mut self, x: c_int, y: c_int) {
         //trace!("Ui::mouse_move({x}, {y})");
         let pos = (x.max(0) as u16, y.max(0) as u16).into();
-        if self.terminal.backend_mut().set_cursor_position(pos) {
+        if self.terminal.backend_mut().set_mouse_position(pos) {
             match self.focus {
                 Focus::Main => {
                     self.handle_touch();
@@ -470,3 +656,42 @@ impl Ui {
         }
     }
 }
+
+#[cfg(any(test, feature = "preview"))]
+impl Ui {
+    pub fn terminal(&self) -> &XashTerminal {
+        &self.terminal
+    }
+}
+
+#[cfg(test)]
+impl Ui {
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn push_menu(&mut self, mut menu: Box<dyn Menu>) {
+        menu.active();
+        self.history.push(menu);
+    }
+}
+
+fn create_quit_popup() -> ConfirmPopup {
+    ConfirmPopup::with_title(
+        i18n::all::QUIT_POPUP_TITLE.localize(),
+        i18n::all::QUIT_POPUP_BODY.localize(),
+    )
+}
+
+fn render_toast(text: &str, area: Rect, buf: &mut Buffer) {
+    let width = (text.width() as u16 + 4).min(area.width);
+    let toast_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.bottom().saturating_sub(3).max(area.y),
+        width,
+        height: 1,
+    }
+    .intersection(area);
+    buf.set_style(toast_area, utils::popup_block_style());
+    Line::raw(text).centered().render(toast_area, buf);
+}
@@ -0,0 +1,217 @@
+use core::ffi::CStr;
+
+use ratatui::prelude::*;
+use xash3d_ui::cvar::CVarFlags;
+
+use crate::prelude::*;
+
+/// Disables animations if non-zero.
+pub const UI_REDUCED_MOTION: &CStr = c"ui_reduced_motion";
+
+/// Duration of menu transitions in seconds.
+pub const MENU_TIME: f32 = 0.15;
+/// Duration of popup open and close animations in seconds.
+pub const POPUP_TIME: f32 = 0.1;
+/// Time in seconds a toast is shown.
+pub const TOAST_TIME: f32 = 3.0;
+/// Period of the highlight pulse in seconds.
+pub const PULSE_TIME: f32 = 1.0;
+
+pub fn init() {
+    engine().register_variable(UI_REDUCED_MOTION, "0", CVarFlags::ARCHIVE);
+}
+
+pub fn is_reduced_motion() -> bool {
+    engine().get_cvar_float(UI_REDUCED_MOTION) != 0.0
+}
+
+/// Returns the progress of the animation started at `start` from 0 to 1.
+///
+/// The animation slows down to the end. Returns 1 if animations are disabled.
+pub fn progress(time: f32, start: f32, duration: f32) -> f32 {
+    if is_reduced_motion() || duration <= 0.0 {
+        return 1.0;
+    }
+    let t = ((time - start) / duration).clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Dims the style periodically.
+pub fn pulse(style: Style, time: f32) -> Style {
+    if !is_reduced_motion() && time % PULSE_TIME >= PULSE_TIME / 2.0 {
+        style.add_modifier(Modifier::DIM)
+    } else {
+        style
+    }
+}
+
+/// Direction of a menu transition.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// A new menu slides in from the right.
+    Forward,
+    /// A previous menu slides in from the left.
+    Backward,
+}
+
+/// Slide and fade of the menu after [Control::Next](super::Control::Next) or
+/// [Control::Back](super::Control::Back).
+///
+/// Only cells are moved, the transition must not be applied to menus with layers of draw
+/// commands.
+#[derive(Copy, Clone, Debug)]
+pub struct Transition {
+    start: f32,
+    direction: Direction,
+}
+
+impl Transition {
+    pub fn new(time: f32, direction: Direction) -> Option<Self> {
+        if is_reduced_motion() {
+            return None;
+        }
+        Some(Self {
+            start: time,
+            direction,
+        })
+    }
+
+    pub fn is_finished(&self, time: f32) -> bool {
+        progress(time, self.start, MENU_TIME) >= 1.0
+    }
+
+    /// Applies the transition to the rendered menu.
+    pub fn apply(&self, buf: &mut Buffer, time: f32) {
+        let progress = progress(time, self.start, MENU_TIME);
+        if progress >= 1.0 {
+            return;
+        }
+        let distance = (buf.area.width / 8) as f32 * (1.0 - progress);
+        let offset = match self.direction {
+            Direction::Forward => distance as i32,
+            Direction::Backward => -(distance as i32),
+        };
+        slide(buf, offset);
+        if progress < 0.5 {
+            // fade in
+            for cell in &mut buf.content {
+                cell.modifier.insert(Modifier::DIM);
+            }
+        }
+    }
+}
+
+/// Moves cells horizontally by `offset`, uncovered cells are cleared.
+fn slide(buf: &mut Buffer, offset: i32) {
+    let width = buf.area.width as usize;
+    if offset == 0 || width == 0 {
+        return;
+    }
+    let shift = offset.unsigned_abs() as usize;
+    for row in buf.content.chunks_mut(width) {
+        if shift >= width {
+            row.iter_mut().for_each(|cell| cell.reset());
+        } else if offset > 0 {
+            row.rotate_right(shift);
+            row[..shift].iter_mut().for_each(|cell| cell.reset());
+        } else {
+            row.rotate_left(shift);
+            row[width - shift..]
+                .iter_mut()
+                .for_each(|cell| cell.reset());
+        }
+    }
+}
+
+/// Open and close animation of a popup.
+///
+/// The popup grows from the center when opened and shrinks when closed.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct PopupAnimation {
+    opened: Option<f32>,
+    closed: Option<f32>,
+}
+
+impl PopupAnimation {
+    /// Starts the close animation.
+    pub fn close(&mut self, time: f32) {
+        self.closed = Some(time);
+    }
+
+    /// Returns `true` while the close animation is running.
+    pub fn is_closing(&self, time: f32) -> bool {
+        self.closed
+            .is_some_and(|start| progress(time, start, POPUP_TIME) < 1.0)
+    }
+
+    /// Returns the visible part of the popup from 0 to 1.
+    ///
+    /// The open animation starts on the first call after the popup was closed.
+    fn progress(&mut self, time: f32) -> f32 {
+        if let Some(start) = self.closed {
+            if self.is_closing(time) {
+                return 1.0 - progress(time, start, POPUP_TIME);
+            }
+            // open again
+            self.closed = None;
+            self.opened = None;
+        }
+        let start = *self.opened.get_or_insert(time);
+        progress(time, start, POPUP_TIME)
+    }
+
+    /// Renders the popup with `render` and hides its part depending on the animation.
+    pub fn render(&mut self, buf: &mut Buffer, time: f32, render: impl FnOnce(&mut Buffer)) {
+        let progress = self.progress(time);
+        if progress >= 1.0 {
+            render(buf);
+            return;
+        }
+        let before = buf.clone();
+        render(buf);
+        reveal(buf, &before, progress);
+    }
+}
+
+/// Restores cells changed after `before` outside of the part scaled by `progress`
+/// around the center of changes.
+fn reveal(buf: &mut Buffer, before: &Buffer, progress: f32) {
+    let area = buf.area;
+    let mut changed: Option<Rect> = None;
+    for pos in area.positions() {
+        if buf[pos] != before[pos] {
+            let cell = Rect::new(pos.x, pos.y, 1, 1);
+            changed = Some(changed.map_or(cell, |i| i.union(cell)));
+        }
+    }
+    let Some(changed) = changed else {
+        return;
+    };
+    let width = (changed.width as f32 * progress).round() as u16;
+    let height = (changed.height as f32 * progress).round() as u16;
+    let visible = Rect::new(
+        changed.x + (changed.width - width) / 2,
+        changed.y + (changed.height - height) / 2,
+        width,
+        height,
+    );
+    for pos in changed.positions() {
+        if !visible.contains(pos) {
+            buf[pos] = before[pos].clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slide_cells() {
+        let mut buf = Buffer::with_lines(["abcd"]);
+        slide(&mut buf, 1);
+        assert_eq!(buf, Buffer::with_lines([" abc"]));
+        slide(&mut buf, -2);
+        assert_eq!(buf, Buffer::with_lines(["bc  "]));
+    }
+}
@@ -0,0 +1,221 @@
+//! Bidirectional text for right-to-left languages.
+//!
+//! Strings are stored in the logical order and reordered to the visual order
+//! right before rendering.
+
+use core::mem;
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_bidi::{BidiClass, Direction, ParagraphBidiInfo};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Returns `true` if the text contains right-to-left characters.
+pub fn has_rtl(text: &str) -> bool {
+    use BidiClass::*;
+
+    text.chars()
+        .any(|c| matches!(unicode_bidi::bidi_class(c), R | AL | RLE | RLO | RLI))
+}
+
+/// Returns `true` if the first strong character of the text is right-to-left.
+pub fn is_rtl(text: &str) -> bool {
+    unicode_bidi::get_base_direction(text) == Direction::Rtl
+}
+
+fn reorder_line(text: &str) -> Cow<'_, str> {
+    if !has_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+    let info = ParagraphBidiInfo::new(text, None);
+    info.reorder_line(0..text.len())
+}
+
+/// Returns the text in the visual order.
+pub fn reorder(text: &str) -> Cow<'_, str> {
+    if !text.contains('\n') {
+        return reorder_line(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&reorder_line(line));
+    }
+    Cow::Owned(out)
+}
+
+/// Returns a line with the text in the visual order.
+///
+/// Right-to-left text is aligned to the right.
+pub fn line(text: &str) -> Line<'static> {
+    let line = Line::raw(reorder(text).into_owned());
+    if is_rtl(text) {
+        line.right_aligned()
+    } else {
+        line
+    }
+}
+
+/// Returns a line with styled spans in the visual order.
+///
+/// Characters keep the style of their span, right-to-left text is aligned to the right.
+pub fn styled_line<'a>(spans: impl IntoIterator<Item = (Style, &'a str)>) -> Line<'static> {
+    let mut text = String::new();
+    let mut styles = Vec::new();
+    for (style, s) in spans {
+        text.push_str(s);
+        styles.extend(s.chars().map(|_| style));
+    }
+    let mut line = Line::default();
+    if text.is_empty() {
+        return line;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let info = ParagraphBidiInfo::new(&text, None);
+    let levels = info.reordered_levels_per_char(0..text.len());
+    let mut span = String::new();
+    let mut current = styles[0];
+    for i in ParagraphBidiInfo::reorder_visual(&levels) {
+        if styles[i] != current && !span.is_empty() {
+            line.push_span(Span::styled(mem::take(&mut span), current));
+        }
+        current = styles[i];
+        span.push(chars[i]);
+    }
+    line.push_span(Span::styled(span, current));
+    if is_rtl(&text) {
+        line.right_aligned()
+    } else {
+        line
+    }
+}
+
+/// Splits the text at spaces to lines not wider than `width`.
+pub fn wrap(text: &str, width: u16) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    let mut w = 0;
+    for word in text.split(' ') {
+        let x = word.width();
+        if w > 0 && w + x > width as usize {
+            // without the trailing space
+            lines.push(&text[start..offset - 1]);
+            start = offset;
+            w = 0;
+        }
+        w += x + 1;
+        offset += word.len() + 1;
+    }
+    lines.push(&text[start..]);
+    lines
+}
+
+/// Returns columns of the text cursor for every cursor position in the logical order.
+///
+/// The cursor is placed before the character in the logical order, that is on the right
+/// side of right-to-left characters.
+pub fn cursor_columns(text: &str) -> Vec<u16> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut columns = vec![0; chars.len() + 1];
+    if chars.is_empty() {
+        return columns;
+    }
+    let info = ParagraphBidiInfo::new(text, None);
+    let levels = info.reordered_levels_per_char(0..text.len());
+    let visual = ParagraphBidiInfo::reorder_visual(&levels);
+    // start and end columns of characters
+    let mut cells = vec![(0, 0); chars.len()];
+    let mut column = 0;
+    for &i in &visual {
+        let width = chars[i].width().unwrap_or(0) as u16;
+        cells[i] = (column, column + width);
+        column += width;
+    }
+    for (i, (start, end)) in cells.iter().enumerate() {
+        columns[i] = if levels[i].is_rtl() { *end } else { *start };
+    }
+    let last = chars.len() - 1;
+    let (start, end) = cells[last];
+    columns[chars.len()] = if levels[last].is_rtl() { start } else { end };
+    columns
+}
+
+/// Returns the cursor position after moving the cursor by one column to the left
+/// or to the right on the screen.
+pub fn move_cursor(columns: &[u16], cursor: usize, right: bool) -> usize {
+    let Some(&current) = columns.get(cursor) else {
+        return cursor;
+    };
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| {
+            if right {
+                **column > current
+            } else {
+                **column < current
+            }
+        })
+        .min_by_key(|(i, column)| (column.abs_diff(current), i.abs_diff(cursor)))
+        .map_or(cursor, |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Alignment, style::Stylize};
+
+    use super::*;
+
+    // "abc" and "אבג" (alef, bet, gimel)
+    const MIXED: &str = "abc \u{5d0}\u{5d1}\u{5d2}";
+
+    #[test]
+    fn reorder_text() {
+        assert_eq!(reorder("abc"), "abc");
+        assert_eq!(reorder(MIXED), "abc \u{5d2}\u{5d1}\u{5d0}");
+        assert_eq!(reorder("\u{5d0}\u{5d1} 12"), "12 \u{5d1}\u{5d0}");
+        assert!(is_rtl("\u{5d0}\u{5d1} abc"));
+        assert!(!is_rtl(MIXED));
+    }
+
+    #[test]
+    fn cursor() {
+        let columns = cursor_columns(MIXED);
+        // the cursor at the start of the hebrew word is on the right side of it
+        assert_eq!(columns, [0, 1, 2, 3, 7, 6, 5, 4]);
+        // move the cursor from the end of the text to the right
+        assert_eq!(move_cursor(&columns, 7, true), 6);
+        assert_eq!(move_cursor(&columns, 4, true), 4);
+        assert_eq!(move_cursor(&columns, 4, false), 5);
+        assert_eq!(move_cursor(&columns, 7, false), 3);
+    }
+
+    #[test]
+    fn styled_spans() {
+        let red = Style::new().red();
+        let blue = Style::new().blue();
+        let line = styled_line([(red, "\u{5d0}\u{5d1}"), (blue, "\u{5d2} "), (red, "ab")]);
+        assert_eq!(line.alignment, Some(Alignment::Right));
+        let spans: Vec<_> = line
+            .spans
+            .iter()
+            .map(|span| (span.style, span.content.as_ref()))
+            .collect();
+        assert_eq!(
+            spans,
+            [(red, "ab"), (blue, " \u{5d2}"), (red, "\u{5d1}\u{5d0}")]
+        );
+    }
+
+    #[test]
+    fn wrap_text() {
+        assert_eq!(wrap("ab cd ef", 5), ["ab cd", "ef"]);
+        assert_eq!(wrap("abcdef", 3), ["abcdef"]);
+    }
+}
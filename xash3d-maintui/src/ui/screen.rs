@@ -1,25 +1,76 @@
-use core::ffi::c_int;
+use core::{
+    cell::{Cell, RefCell},
+    ffi::c_int,
+};
 
-use ratatui::layout::{Position, Rect, Size};
-use xash3d_ratatui::XashBackend;
+use alloc::vec::Vec;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect, Size},
+};
+use xash3d_ratatui::{AtlasStats, DrawCommand, Layer, XashBackend};
 use xash3d_ui::{color::RGBA, misc::Rect as UiRect, picture::Picture};
 
-use crate::prelude::*;
-
-pub struct Screen {
+pub struct Screen<'a> {
+    backend: &'a XashBackend,
     /// Cell size in pixels.
     pub cell: Size,
+    /// Font size in pixels.
+    pub font_size: u16,
     pub cursor: Position,
+    /// Time of the current frame in seconds.
+    pub time: f32,
+    layers: RefCell<Vec<Layer>>,
+    text_cursor: Cell<Option<Position>>,
 }
 
-impl Screen {
-    pub fn new(backend: &XashBackend) -> Self {
+impl<'a> Screen<'a> {
+    pub fn new(backend: &'a XashBackend) -> Self {
         Screen {
+            backend,
             cell: backend.cell_size_in_pixels(),
+            font_size: backend.get_font_size(),
             cursor: backend.cursor_position(),
+            time: backend.time(),
+            layers: RefCell::default(),
+            text_cursor: Cell::default(),
         }
     }
 
+    /// Returns `true` if layers of draw commands were added.
+    pub fn has_layers(&self) -> bool {
+        !self.layers.borrow().is_empty()
+    }
+
+    /// Returns glyph atlas statistics for debugging.
+    pub fn atlas_stats(&self) -> AtlasStats {
+        self.backend.atlas_stats()
+    }
+
+    /// Returns layers of draw commands added while menus were rendered.
+    pub fn into_layers(self) -> Vec<Layer> {
+        self.layers.into_inner()
+    }
+
+    /// Returns the text cursor position set while menus were rendered.
+    pub fn text_cursor(&self) -> Option<Position> {
+        self.text_cursor.get()
+    }
+
+    /// Shows the text cursor at the cell position in the current frame.
+    pub fn set_text_cursor(&self, position: Position) {
+        self.text_cursor.set(Some(position));
+    }
+
+    /// Defers engine drawing until the buffer is drawn.
+    ///
+    /// Widgets rendered later over the area are drawn on top of the commands.
+    pub fn push_layer(&self, area: Rect, buf: &Buffer, commands: Vec<DrawCommand>) {
+        self.layers
+            .borrow_mut()
+            .push(Layer::new(area, buf, commands));
+    }
+
     pub fn area_to_pixels(&self, area: Rect) -> Rect {
         Rect {
             x: area.x * self.cell.width,
@@ -29,14 +80,17 @@ impl Screen {
         }
     }
 
-    pub fn draw_picture(&self, area: Rect, pic: Picture, colors: &[RGBA]) {
-        let engine = engine();
+    pub fn draw_picture(&self, area: Rect, buf: &Buffer, pic: Picture, colors: &[RGBA]) {
+        let cells = area;
         let area = self.area_to_pixels(area);
         let mut x = area.x as i32;
         let mut y = area.y as i32;
         let mut w = area.width as u32;
         let mut h = area.height as u32;
-        engine.fill_rgba(RGBA::BLACK, UiRect::new(x, y, w, h));
+        let mut commands = vec![DrawCommand::Fill {
+            color: RGBA::BLACK,
+            area: UiRect::new(x, y, w, h),
+        }];
 
         let size = pic.size();
         let r = size.width as f32 / size.height as f32;
@@ -50,8 +104,12 @@ impl Screen {
             w = t;
         }
         if colors.is_empty() {
-            let area = UiRect::new(x, y, w, h);
-            pic.draw(RGBA::WHITE, area, None);
+            commands.push(DrawCommand::Picture {
+                pic,
+                color: RGBA::WHITE,
+                area: UiRect::new(x, y, w, h),
+                src: None,
+            });
         } else {
             let len = colors.len() as f64;
             let y_step = h as f64 / len;
@@ -65,9 +123,14 @@ impl Screen {
                     ((i + 1.0) * r_step).round() as u32,
                 );
                 let y = y + (i * y_step).round() as c_int;
-                let area = UiRect::new(x, y, w, y_step as u32);
-                pic.draw(*color, area, Some(pic_area));
+                commands.push(DrawCommand::Picture {
+                    pic,
+                    color: *color,
+                    area: UiRect::new(x, y, w, y_step as u32),
+                    src: Some(pic_area),
+                });
             }
         }
+        self.push_layer(cells, buf, commands);
     }
 }
@@ -0,0 +1,306 @@
+use core::{ffi::CStr, fmt::Write, str::FromStr};
+
+use alloc::vec::Vec;
+use compact_str::{CompactString, ToCompactString};
+use csz::CStrArray;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use xash3d_ratatui::Palette;
+use xash3d_ui::{
+    cvar::CVarFlags,
+    parser::{TokenError, Tokens},
+};
+
+use crate::{
+    prelude::*,
+    strings,
+    ui::utils::{self, Registry},
+};
+
+pub const UI_THEME: &CStr = c"ui_theme";
+
+const DEFAULT_THEME: &str = "default";
+const HIGH_CONTRAST_THEME: &str = "high-contrast";
+
+/// Colors and semantic styles used by menus.
+pub struct Theme {
+    name: CompactString,
+    pub palette: Palette,
+    /// Borders of menu blocks.
+    pub border: Style,
+    /// Titles of menu blocks.
+    pub title: Style,
+    /// Focused item.
+    pub highlight: Style,
+    /// Focused item grabbed for editing.
+    pub grabbed: Style,
+    /// Hints for focused items.
+    pub hint: Style,
+    /// Popup blocks.
+    pub popup: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_THEME.into(),
+            palette: Palette::default(),
+            border: Style::new().yellow(),
+            title: Style::new().yellow(),
+            highlight: Style::new().black().on_yellow(),
+            grabbed: Style::new().black().on_green(),
+            hint: Style::new().gray(),
+            popup: Style::new().black().on_gray(),
+        }
+    }
+}
+
+impl Theme {
+    fn high_contrast() -> Self {
+        Self {
+            name: HIGH_CONTRAST_THEME.into(),
+            palette: Palette {
+                colors: [
+                    [0x00, 0x00, 0x00],
+                    [0xff, 0x40, 0x40],
+                    [0x40, 0xff, 0x40],
+                    [0xff, 0xff, 0x00],
+                    [0x60, 0x80, 0xff],
+                    [0xff, 0x40, 0xff],
+                    [0x00, 0xff, 0xff],
+                    [0xd0, 0xd0, 0xd0],
+                    [0x80, 0x80, 0x80],
+                    [0xff, 0x80, 0x80],
+                    [0x80, 0xff, 0x80],
+                    [0xff, 0xff, 0x80],
+                    [0x80, 0xa0, 0xff],
+                    [0xff, 0x80, 0xff],
+                    [0x80, 0xff, 0xff],
+                    [0xff, 0xff, 0xff],
+                ],
+                fg: [0xff, 0xff, 0xff],
+                bg: [0x00, 0x00, 0x00],
+                alpha: 0xff,
+            },
+            border: Style::new().white(),
+            title: Style::new().yellow().bold(),
+            highlight: Style::new().black().on_yellow(),
+            grabbed: Style::new().black().on_cyan(),
+            hint: Style::new().white(),
+            popup: Style::new().black().on_white(),
+        }
+    }
+
+    fn load(name: &str) -> Self {
+        match name {
+            DEFAULT_THEME => return Self::default(),
+            HIGH_CONTRAST_THEME => return Self::high_contrast(),
+            _ => {}
+        }
+
+        // missing values are taken from the default theme
+        let mut theme = Self {
+            name: name.into(),
+            ..Self::default()
+        };
+        let mut path = CStrArray::<128>::new();
+        if path
+            .cursor()
+            .write_fmt(format_args!("resource/maintui_theme_{name}.txt"))
+            .is_err()
+        {
+            error!("theme name is too long \"{name}\"");
+            return theme;
+        }
+        let Ok(file) = engine().load_file(&path) else {
+            error!("failed to open {path}");
+            return theme;
+        };
+        let src = strings::bytes_to_string(file.as_bytes());
+        if let Err(err) = theme.parse(&src) {
+            error!("failed to parse {path}, {err:?}");
+        }
+        theme
+    }
+
+    fn parse<'a>(&mut self, src: &'a str) -> Result<(), TokenError<'a>> {
+        let mut tokens = Tokens::new(src);
+        tokens.expect("theme")?;
+        tokens.expect("{")?;
+        loop {
+            let section = tokens.parse()?;
+            if section == "}" {
+                break;
+            }
+            tokens.expect("{")?;
+            loop {
+                let name = tokens.parse()?;
+                if name == "}" {
+                    break;
+                }
+                let value = tokens.parse()?;
+                let ok = match section {
+                    "palette" => self.set_color(name, value),
+                    "styles" => self.set_style(name, value),
+                    _ => false,
+                };
+                if !ok {
+                    warn!(
+                        "theme {}: invalid {section} \"{name}\" \"{value}\"",
+                        self.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_color(&mut self, name: &str, value: &str) -> bool {
+        // the background may have an alpha channel in the form #rrggbbaa
+        let (value, alpha) = match (name, value.split_at_checked(7)) {
+            ("background", Some((value, alpha))) if alpha.len() == 2 => {
+                let Ok(alpha) = u8::from_str_radix(alpha, 16) else {
+                    return false;
+                };
+                (value, Some(alpha))
+            }
+            _ => (value, None),
+        };
+        let Ok(Color::Rgb(r, g, b)) = Color::from_str(value) else {
+            return false;
+        };
+        let color = match name {
+            "foreground" => &mut self.palette.fg,
+            "background" => &mut self.palette.bg,
+            _ => match Color::from_str(name).ok().and_then(Palette::base_index) {
+                Some(i) => &mut self.palette.colors[i],
+                None => return false,
+            },
+        };
+        *color = [r, g, b];
+        if let Some(alpha) = alpha {
+            self.palette.alpha = alpha;
+        }
+        true
+    }
+
+    fn set_style(&mut self, name: &str, value: &str) -> bool {
+        let style = match name {
+            "border" => &mut self.border,
+            "title" => &mut self.title,
+            "highlight" => &mut self.highlight,
+            "grabbed" => &mut self.grabbed,
+            "hint" => &mut self.hint,
+            "popup" => &mut self.popup,
+            _ => return false,
+        };
+        match parse_style(value) {
+            Some(s) => {
+                *style = s;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Parses a style in the form `[FG] [on BG] [MODIFIERS...]`.
+fn parse_style(s: &str) -> Option<Style> {
+    let mut style = Style::new();
+    let mut words = s.split_whitespace();
+    while let Some(word) = words.next() {
+        let modifier = match word {
+            "on" => {
+                style = style.bg(Color::from_str(words.next()?).ok()?);
+                continue;
+            }
+            "bold" => Modifier::BOLD,
+            "dim" => Modifier::DIM,
+            "italic" => Modifier::ITALIC,
+            "underlined" => Modifier::UNDERLINED,
+            "reversed" => Modifier::REVERSED,
+            "crossed_out" => Modifier::CROSSED_OUT,
+            _ => {
+                style = style.fg(Color::from_str(word).ok()?);
+                continue;
+            }
+        };
+        style = style.add_modifier(modifier);
+    }
+    Some(style)
+}
+
+static THEMES: Registry<Theme> = Registry::new();
+
+/// Loads themes again, the fake engine has new files in every test.
+#[cfg(test)]
+pub fn reset() {
+    THEMES.reset();
+}
+
+pub fn init() {
+    engine().register_variable(UI_THEME, DEFAULT_THEME, CVarFlags::ARCHIVE);
+}
+
+pub fn theme() -> &'static Theme {
+    THEMES
+        .current()
+        .unwrap_or_else(|| THEMES.select(DEFAULT_THEME, Theme::default))
+}
+
+/// Loads a new theme if the cvar was changed.
+pub fn update() -> Option<&'static Theme> {
+    let name = engine().get_cvar_string(UI_THEME);
+    let name = name.to_str().unwrap_or(DEFAULT_THEME);
+    if name == theme().name {
+        return None;
+    }
+    trace!("select theme \"{name}\"");
+    Some(THEMES.select(name, || Theme::load(name)))
+}
+
+pub fn set_name(name: &str) {
+    let mut buf = CStrArray::<128>::new();
+    if buf.cursor().write_str(name).is_ok() {
+        engine().set_cvar_string(UI_THEME, &buf);
+    }
+}
+
+/// Returns built-in themes and themes found in the game filesystem.
+pub fn list() -> Vec<CompactString> {
+    let mut list: Vec<CompactString> = vec![DEFAULT_THEME.into(), HIGH_CONTRAST_THEME.into()];
+    let files = engine().get_files_list(c"resource/maintui_theme_*.txt", false);
+    for i in files.iter() {
+        let Some(name) = i
+            .to_str()
+            .ok()
+            .and_then(utils::file_stem)
+            .and_then(|i| i.strip_prefix("maintui_theme_"))
+        else {
+            continue;
+        };
+        if !list.iter().any(|i| i == name) {
+            list.push(name.to_compact_string());
+        }
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_colors() {
+        let mut theme = Theme::default();
+        assert!(!theme.set_color("background", "blue"));
+        assert!(!theme.set_color("background", "#1234567g"));
+        assert!(!theme.set_color("background", "#xxxxxx80"));
+        assert!(!theme.set_color("unknown", "#123456"));
+        assert_eq!(theme.palette.alpha, Palette::default().alpha);
+        assert_eq!(theme.palette.bg, Palette::default().bg);
+
+        assert!(theme.set_color("background", "#10203040"));
+        assert_eq!(theme.palette.bg, [0x10, 0x20, 0x30]);
+        assert_eq!(theme.palette.alpha, 0x40);
+    }
+}
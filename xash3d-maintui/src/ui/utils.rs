@@ -1,25 +1,32 @@
-use core::cmp;
+use core::{
+    cmp, iter, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
 
-use alloc::string::String;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use compact_str::CompactString;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, ListState, Paragraph, TableState, Wrap},
 };
 use unicode_width::UnicodeWidthStr;
 
-use crate::{prelude::*, strings};
+use crate::{
+    prelude::*,
+    strings,
+    ui::{bidi, theme::theme},
+};
 
 pub fn is_wide(area: Rect) -> bool {
     area.width >= 80
 }
 
 pub fn main_block_border_style() -> Style {
-    Style::new().yellow()
+    theme().border
 }
 
-pub fn main_block(title: &str, area: Rect, buf: &mut Buffer) -> Rect {
-    let block = Block::default()
-        .title(strings::get(title).yellow())
+fn main_block_inner(block: Block, area: Rect, buf: &mut Buffer) -> Rect {
+    let block = block
         .borders(Borders::ALL)
         .border_style(main_block_border_style());
     let inner_area = block.inner(area);
@@ -27,13 +34,35 @@ pub fn main_block(title: &str, area: Rect, buf: &mut Buffer) -> Rect {
     inner_area
 }
 
+pub fn main_block(title: &str, area: Rect, buf: &mut Buffer) -> Rect {
+    let block = Block::default().title(bidi::line(strings::get(title)).style(theme().title));
+    main_block_inner(block, area, buf)
+}
+
+/// Same as [main_block] with a status on the right side of the top border.
+pub fn main_block_with_status(title: &str, status: &str, area: Rect, buf: &mut Buffer) -> Rect {
+    let title = bidi::line(strings::get(title)).style(theme().title);
+    let status = bidi::line(status).style(theme().title);
+    // the status is on the opposite side of the title
+    let status = if title.alignment == Some(Alignment::Right) {
+        status.left_aligned()
+    } else {
+        status.right_aligned()
+    };
+    let block = Block::default().title(title).title(status);
+    main_block_inner(block, area, buf)
+}
+
 pub fn popup_block_style() -> Style {
-    Style::default().black().on_gray()
+    theme().popup
 }
 
 pub fn popup_block(title: &str) -> Block<'_> {
     Block::default()
-        .title(strings::get(title).black())
+        .title(Span::styled(
+            bidi::reorder(strings::get(title)),
+            popup_block_style(),
+        ))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
         .border_style(popup_block_style())
@@ -165,9 +194,17 @@ pub fn render_hint(area: Rect, buf: &mut Buffer, items: usize, hint: Option<&str
             let [area, hint_area] =
                 Layout::vertical([Constraint::Percentage(100), Constraint::Min(hint_height)])
                     .areas(area);
-            Paragraph::new(hint.gray())
-                .wrap(Wrap { trim: true })
-                .render(hint_area, buf);
+            if bidi::is_rtl(hint) {
+                // wrap before reordering to keep the order of lines
+                let lines = bidi::wrap(hint, area.width).into_iter().map(bidi::line);
+                Paragraph::new(lines.collect::<Vec<_>>())
+                    .style(theme().hint)
+                    .render(hint_area, buf);
+            } else {
+                Paragraph::new(Span::styled(bidi::reorder(hint), theme().hint))
+                    .wrap(Wrap { trim: true })
+                    .render(hint_area, buf);
+            }
             return area;
         }
     }
@@ -228,6 +265,70 @@ pub fn file_extension(path: &str) -> Option<&str> {
     if stem.is_empty() { None } else { Some(ext) }
 }
 
+struct RegistryEntry<T: 'static> {
+    name: CompactString,
+    value: T,
+    next: Option<&'static RegistryEntry<T>>,
+}
+
+/// Global values loaded by name, one of them is selected.
+///
+/// Menus and widgets borrow the selected value for the rest of the program so values are
+/// never freed. Loaded values are reused, the memory is bounded by the number of names.
+pub struct Registry<T: 'static> {
+    current: AtomicPtr<RegistryEntry<T>>,
+    /// The last loaded entry, entries are linked through [RegistryEntry::next].
+    last: AtomicPtr<RegistryEntry<T>>,
+}
+
+impl<T> Registry<T> {
+    pub const fn new() -> Self {
+        Self {
+            current: AtomicPtr::new(ptr::null_mut()),
+            last: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &'static RegistryEntry<T>> {
+        // SAFETY: entries are never freed
+        let last = unsafe { self.last.load(Ordering::Relaxed).as_ref() };
+        iter::successors(last, |entry| entry.next)
+    }
+
+    /// Returns the selected value.
+    pub fn current(&self) -> Option<&'static T> {
+        // SAFETY: entries are never freed
+        let current = unsafe { self.current.load(Ordering::Relaxed).as_ref() };
+        current.map(|entry| &entry.value)
+    }
+
+    /// Selects the value with the name, `load` is called if it was not loaded yet.
+    pub fn select(&self, name: &str, load: impl FnOnce() -> T) -> &'static T {
+        let entry = match self.entries().find(|entry| entry.name == name) {
+            Some(entry) => entry,
+            None => {
+                let entry = Box::leak(Box::new(RegistryEntry {
+                    name: name.into(),
+                    value: load(),
+                    next: self.entries().next(),
+                }));
+                self.last.store(entry, Ordering::Relaxed);
+                entry
+            }
+        };
+        self.current
+            .store(ptr::from_ref(entry).cast_mut(), Ordering::Relaxed);
+        &entry.value
+    }
+
+    /// Forgets loaded values, the fake engine has new files in every test.
+    #[cfg(test)]
+    pub fn reset(&self) {
+        self.current.store(ptr::null_mut(), Ordering::Relaxed);
+        self.last.store(ptr::null_mut(), Ordering::Relaxed);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -1,4 +1,5 @@
 mod button;
+mod canvas;
 mod checkbox;
 mod image;
 mod input;
@@ -15,6 +16,7 @@ use ratatui::prelude::*;
 use xash3d_ratatui::XashBackend;
 
 pub use self::button::Button;
+pub use self::canvas::{Canvas, CanvasMode};
 pub use self::checkbox::Checkbox;
 pub use self::image::Image;
 pub use self::input::Input;
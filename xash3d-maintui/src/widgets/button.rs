@@ -1,7 +1,11 @@
 use alloc::string::String;
 use ratatui::prelude::*;
 
-use crate::{i18n, strings::strings};
+use crate::{
+    i18n,
+    strings::strings,
+    ui::{bidi, theme::theme},
+};
 
 pub struct Button {
     pub area: Rect,
@@ -12,7 +16,7 @@ impl Button {
     pub fn new(label: &str) -> Self {
         Self {
             area: Rect::ZERO,
-            label: format!("{label:^21}"),
+            label: format!("{:^21}", bidi::reorder(label)),
         }
     }
 
@@ -26,7 +30,7 @@ impl Button {
 
     pub fn render(&mut self, area: Rect, buf: &mut Buffer, focused: bool) {
         let style = if focused {
-            Style::default().black().on_yellow()
+            theme().highlight
         } else {
             Style::default().white().on_black()
         };
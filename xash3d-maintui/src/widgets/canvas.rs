@@ -0,0 +1,258 @@
+use alloc::vec::Vec;
+use ratatui::prelude::*;
+use xash3d_ratatui::{DrawCommand, XashBackend};
+use xash3d_ui::{color::RGBA, misc::Rect as UiRect};
+
+use crate::{
+    input::KeyEvent,
+    ui::Screen,
+    widgets::{ConfirmResult, WidgetMut},
+};
+
+const UPPER_HALF_BLOCK: &str = "\u{2580}";
+const LOWER_HALF_BLOCK: &str = "\u{2584}";
+
+/// Bits of braille dots in a cell of 2x4 dots.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// How the canvas is drawn on the screen.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum CanvasMode {
+    /// Filled rectangles in screen pixels.
+    #[default]
+    Native,
+    /// Half block glyphs with 1x2 pixels per cell.
+    HalfBlock,
+    /// Braille glyphs with 2x4 pixels per cell and one color per cell.
+    Braille,
+}
+
+/// Pixel canvas scaled to the widget area.
+///
+/// Transparent pixels show the default background.
+pub struct Canvas {
+    width: u16,
+    height: u16,
+    pixels: Vec<Option<[u8; 3]>>,
+    mode: CanvasMode,
+}
+
+impl Canvas {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![None; width as usize * height as usize],
+            mode: CanvasMode::default(),
+        }
+    }
+
+    pub fn mode(&self) -> CanvasMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: CanvasMode) {
+        self.mode = mode;
+    }
+
+    pub fn get(&self, x: u16, y: u16) -> Option<[u8; 3]> {
+        if x < self.width && y < self.height {
+            self.pixels[y as usize * self.width as usize + x as usize]
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of the pixel, pixels outside of the canvas are ignored.
+    pub fn set(&mut self, x: u16, y: u16, color: Option<[u8; 3]>) {
+        if x < self.width && y < self.height {
+            self.pixels[y as usize * self.width as usize + x as usize] = color;
+        }
+    }
+
+    pub fn fill_rect(&mut self, x: u16, y: u16, width: u16, height: u16, color: [u8; 3]) {
+        for y in y..y.saturating_add(height).min(self.height) {
+            for x in x..x.saturating_add(width).min(self.width) {
+                self.set(x, y, Some(color));
+            }
+        }
+    }
+
+    /// Draws a line between two pixels including both ends.
+    pub fn line(&mut self, from: (u16, u16), to: (u16, u16), color: [u8; 3]) {
+        let (mut x, mut y) = (from.0 as i32, from.1 as i32);
+        let (x1, y1) = (to.0 as i32, to.1 as i32);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.set(x as u16, y as u16, Some(color));
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Returns the pixel for a position in the grid of `width`x`height` pixels.
+    fn sample(&self, x: u16, y: u16, width: u16, height: u16) -> Option<[u8; 3]> {
+        let x = x as u32 * self.width as u32 / width as u32;
+        let y = y as u32 * self.height as u32 / height as u32;
+        self.get(x as u16, y as u16)
+    }
+
+    fn render_native(&self, area: Rect, buf: &Buffer, screen: &Screen) {
+        let pixels = screen.area_to_pixels(area);
+        let (x0, y0) = (pixels.x as u32, pixels.y as u32);
+        let (w, h) = (pixels.width as u32, pixels.height as u32);
+        let (width, height) = (self.width as u32, self.height as u32);
+        let mut commands = Vec::new();
+        for y in 0..height {
+            let top = y0 + y * h / height;
+            let bottom = y0 + (y + 1) * h / height;
+            let mut x = 0;
+            while x < width {
+                let start = x;
+                let color = self.get(x as u16, y as u16);
+                // merge pixels with the same color to a single rectangle
+                while x < width && self.get(x as u16, y as u16) == color {
+                    x += 1;
+                }
+                let Some([r, g, b]) = color else {
+                    continue;
+                };
+                let left = x0 + start * w / width;
+                let right = x0 + x * w / width;
+                if right > left && bottom > top {
+                    commands.push(DrawCommand::Fill {
+                        color: RGBA::rgb(r, g, b),
+                        area: UiRect::new(left as i32, top as i32, right - left, bottom - top),
+                    });
+                }
+            }
+        }
+        screen.push_layer(area, buf, commands);
+    }
+
+    fn render_half_block(&self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = (area.width, area.height * 2);
+        for pos in area.positions() {
+            let (x, y) = (pos.x - area.x, pos.y - area.y);
+            let top = self.sample(x, y * 2, width, height);
+            let bottom = self.sample(x, y * 2 + 1, width, height);
+            let cell = &mut buf[pos];
+            match (top, bottom) {
+                (Some([r, g, b]), bottom) => {
+                    cell.set_symbol(UPPER_HALF_BLOCK)
+                        .set_fg(Color::Rgb(r, g, b));
+                    if let Some([r, g, b]) = bottom {
+                        cell.set_bg(Color::Rgb(r, g, b));
+                    }
+                }
+                (None, Some([r, g, b])) => {
+                    cell.set_symbol(LOWER_HALF_BLOCK)
+                        .set_fg(Color::Rgb(r, g, b));
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn render_braille(&self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = (area.width * 2, area.height * 4);
+        for pos in area.positions() {
+            let (x, y) = ((pos.x - area.x) * 2, (pos.y - area.y) * 4);
+            let mut bits = 0;
+            let mut sum = [0u32; 3];
+            let mut count = 0;
+            for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+                for (dx, bit) in row.iter().enumerate() {
+                    let Some(color) = self.sample(x + dx as u16, y + dy as u16, width, height)
+                    else {
+                        continue;
+                    };
+                    bits |= bit;
+                    for (sum, c) in sum.iter_mut().zip(color) {
+                        *sum += c as u32;
+                    }
+                    count += 1;
+                }
+            }
+            if count == 0 {
+                continue;
+            }
+            // one color per cell, use the average of set dots
+            let [r, g, b] = sum.map(|i| (i / count) as u8);
+            let symbol = char::from_u32(0x2800 + bits as u32).unwrap();
+            buf[pos].set_char(symbol).set_fg(Color::Rgb(r, g, b));
+        }
+    }
+
+    /// Renders the canvas in text modes, does nothing in the native mode.
+    fn render_cells(&self, area: Rect, buf: &mut Buffer) {
+        match self.mode {
+            CanvasMode::Native => {}
+            CanvasMode::HalfBlock => self.render_half_block(area, buf),
+            CanvasMode::Braille => self.render_braille(area, buf),
+        }
+    }
+}
+
+impl WidgetMut<ConfirmResult> for Canvas {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
+        for pos in area.positions() {
+            buf[pos].reset();
+        }
+        if area.is_empty() || self.width == 0 || self.height == 0 {
+            return;
+        }
+        match self.mode {
+            CanvasMode::Native => self.render_native(area, buf, screen),
+            _ => self.render_cells(area, buf),
+        }
+    }
+
+    fn key_event(&mut self, _: &XashBackend, _: KeyEvent) -> ConfirmResult {
+        ConfirmResult::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(canvas: &mut Canvas, mode: CanvasMode) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        canvas.set_mode(mode);
+        canvas.render_cells(buf.area, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn text_modes() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((0, 0), (3, 3), [255, 0, 0]);
+        canvas.fill_rect(2, 0, 1, 1, [0, 0, 255]);
+
+        let buf = render(&mut canvas, CanvasMode::HalfBlock);
+        assert_eq!(buf[(0, 0)].symbol(), UPPER_HALF_BLOCK);
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(255, 0, 0));
+        assert_eq!(buf[(1, 0)].symbol(), UPPER_HALF_BLOCK);
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(0, 0, 255));
+        assert_eq!(buf[(1, 0)].bg, Color::Rgb(255, 0, 0));
+
+        let buf = render(&mut canvas, CanvasMode::Braille);
+        assert_eq!(buf[(0, 0)].symbol(), "\u{2811}");
+        assert_eq!(buf[(1, 0)].symbol(), "\u{2885}");
+    }
+}
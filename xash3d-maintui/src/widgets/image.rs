@@ -29,7 +29,7 @@ impl WidgetMut<ConfirmResult> for Image<'_> {
             buf[pos].reset();
         }
         if !self.pic.is_none() {
-            screen.draw_picture(area, self.pic, self.colors);
+            screen.draw_picture(area, buf, self.pic, self.colors);
         }
     }
 
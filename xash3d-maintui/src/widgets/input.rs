@@ -1,13 +1,15 @@
 use core::cmp;
 
+use alloc::borrow::Cow;
 use compact_str::CompactString;
 use ratatui::prelude::*;
+use unicode_width::UnicodeWidthStr;
 use xash3d_ratatui::XashBackend;
 
 use crate::{
     input::{Key, KeyEvent},
     prelude::*,
-    ui::Screen,
+    ui::{Screen, bidi},
     widgets::{ConfirmResult, Value, WidgetMut},
 };
 
@@ -74,12 +76,33 @@ impl Input {
             .map_or(self.value.len(), |i| i.0)
     }
 
+    /// Returns `true` if the value is shown in the visual order.
+    fn is_bidi(&self) -> bool {
+        !self.password && bidi::has_rtl(&self.value)
+    }
+
+    /// Moves the cursor on the screen instead of the logical order of characters.
+    fn cursor_move_visual(&mut self, n: u16, right: bool) {
+        let columns = bidi::cursor_columns(&self.value);
+        for _ in 0..n {
+            self.cursor = bidi::move_cursor(&columns, self.cursor as usize, right) as u16;
+        }
+    }
+
     pub fn cursor_left(&mut self, n: u16) {
-        self.cursor = self.cursor.saturating_sub(n);
+        if self.is_bidi() {
+            self.cursor_move_visual(n, false);
+        } else {
+            self.cursor = self.cursor.saturating_sub(n);
+        }
     }
 
     pub fn cursor_right(&mut self, n: u16) {
-        self.cursor = cmp::min(self.cursor + n, self.value.chars().count() as u16);
+        if self.is_bidi() {
+            self.cursor_move_visual(n, true);
+        } else {
+            self.cursor = cmp::min(self.cursor + n, self.value.chars().count() as u16);
+        }
     }
 
     pub fn cursor_to_start(&mut self) {
@@ -102,12 +125,13 @@ impl Input {
 }
 
 impl WidgetMut<ConfirmResult> for Input {
-    fn render(&mut self, area: Rect, buf: &mut Buffer, _: &Screen) {
-        let mut s = self.value.as_str();
-        if self.password {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
+        let s = if self.password {
             let width = self.value.chars().count();
-            s = &PASSWORD[..width];
-        }
+            Cow::Borrowed(&PASSWORD[..width])
+        } else {
+            bidi::reorder(&self.value)
+        };
         let style = if self.show_cursor {
             Style::default().white().on_dark_gray()
         } else {
@@ -116,20 +140,16 @@ impl WidgetMut<ConfirmResult> for Input {
         Line::raw(SPACES).style(style).render(area, buf);
         Line::raw(s).style(style).render(area, buf);
 
-        if self.show_cursor && self.cursor < area.width {
-            let offset = self.cursor_to_offset();
-            let cursor_area = Rect {
-                x: area.x + self.cursor,
-                width: 1,
-                ..area
-            };
-            let mut s = &self.value[offset..];
-            if s.is_empty() {
-                s = " ";
-            }
-            Line::raw(s)
-                .style(Style::default().on_red())
-                .render(cursor_area, buf);
+        let offset = self.cursor_to_offset();
+        let column = if self.password {
+            self.cursor
+        } else if self.is_bidi() {
+            bidi::cursor_columns(&self.value)[self.cursor as usize]
+        } else {
+            self.value[..offset].width() as u16
+        };
+        if self.show_cursor && column < area.width {
+            screen.set_text_cursor(Position::new(area.x + column, area.y));
         }
     }
 
@@ -218,3 +238,53 @@ impl Value<CompactString> for Input {
         self.cursor_to_end();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::string::String;
+
+    use super::*;
+    use crate::{input::Modifier, tests::Harness};
+
+    // "abc" and "אבג" (alef, bet, gimel)
+    const MIXED: &str = "abc \u{5d0}\u{5d1}\u{5d2}";
+
+    fn press(input: &mut Input, backend: &XashBackend, key: Key) {
+        let event = KeyEvent::with_key(0, Modifier::default(), true, key);
+        input.key_event(backend, event);
+    }
+
+    #[test]
+    fn mixed_direction_keys() {
+        let h = Harness::new();
+        let backend = h.backend();
+        let mut input = Input::new();
+        input.set_value(MIXED.into());
+        assert_eq!(input.cursor, 7);
+
+        // arrows move the cursor on the screen, the end of the hebrew word is on the left
+        press(&mut input, backend, Key::ArrowRight);
+        assert_eq!(input.cursor, 6);
+        press(&mut input, backend, Key::ArrowLeft);
+        assert_eq!(input.cursor, 7);
+        press(&mut input, backend, Key::ArrowLeft);
+        assert_eq!(input.cursor, 3);
+
+        // editing keys work in the logical order
+        press(&mut input, backend, Key::Backspace);
+        assert_eq!(input.value(), "ab \u{5d0}\u{5d1}\u{5d2}");
+        press(&mut input, backend, Key::Delete);
+        assert_eq!(input.value(), "ab\u{5d0}\u{5d1}\u{5d2}");
+        press(&mut input, backend, Key::End);
+        press(&mut input, backend, Key::Char(b'x'));
+        assert_eq!(input.value(), "ab\u{5d0}\u{5d1}\u{5d2}x");
+        press(&mut input, backend, Key::Home);
+        press(&mut input, backend, Key::ArrowLeft);
+        assert_eq!(input.cursor, 0);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        input.render(buf.area, &mut buf, &Screen::new(backend));
+        let line: String = (0..6).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(line, "ab\u{5d2}\u{5d1}\u{5d0}x");
+    }
+}
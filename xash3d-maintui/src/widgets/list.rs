@@ -11,7 +11,7 @@ use xash3d_ratatui::XashBackend;
 use crate::{
     input::{Key, KeyEvent},
     strings::strings,
-    ui::{Screen, sound, symbols, utils::Scroll},
+    ui::{Screen, bidi, sound, symbols, theme::theme, utils::Scroll},
     widgets::{Scrollbar, SelectResult, WidgetMut},
 };
 
@@ -77,7 +77,8 @@ impl DerefMut for ListState {
 
 pub struct List {
     style: Style,
-    highlight_style: Style,
+    /// Uses the theme if not set.
+    highlight_style: Option<Style>,
     pub area: Rect,
     pub state: ListState,
     items: Vec<CompactString>,
@@ -89,10 +90,7 @@ impl List {
     pub fn new<T: ToCompactString>(items: impl IntoIterator<Item = T>) -> Self {
         Self {
             style: Style::new(),
-            highlight_style: Style::new()
-                .add_modifier(Modifier::BOLD)
-                .black()
-                .on_yellow(),
+            highlight_style: None,
             area: Rect::ZERO,
             state: ListState::new(),
             items: items.into_iter().map(|i| i.to_compact_string()).collect(),
@@ -136,7 +134,7 @@ impl List {
     }
 
     pub fn set_highlight_style(&mut self, style: Style) {
-        self.highlight_style = style;
+        self.highlight_style = Some(style);
     }
 
     pub fn cursor_to_item(&self, backend: &XashBackend) -> Option<usize> {
@@ -179,6 +177,9 @@ impl List {
     fn create_line(&self, item: &str) -> Line<'static> {
         let strings = strings();
         let s = strings.get(item);
+        if bidi::has_rtl(s) {
+            return bidi::line(s);
+        }
         if let Some(Key::Char(c)) = self.get_binding(item) {
             if c.is_ascii_alphanumeric() {
                 if let Some((i, _)) = s
@@ -202,7 +203,6 @@ impl List {
         let items: Vec<_> = self.items.iter().map(|i| self.create_line(i)).collect();
         self.list = ratatui::widgets::List::new(items)
             .style(self.style)
-            .highlight_style(self.highlight_style)
             .highlight_symbol(symbols::HIGHLIGHT_SYMBOL)
             .highlight_spacing(HighlightSpacing::Always)
             .into();
@@ -221,8 +221,13 @@ impl WidgetMut<SelectResult> for List {
             self.init_list();
         }
 
-        if let Some(list) = self.list.as_ref() {
-            StatefulWidgetRef::render_ref(list, self.area, buf, &mut self.state);
+        if let Some(mut list) = self.list.take() {
+            let highlight_style = self
+                .highlight_style
+                .unwrap_or_else(|| theme().highlight.add_modifier(Modifier::BOLD));
+            list = list.highlight_style(highlight_style);
+            StatefulWidgetRef::render_ref(&list, self.area, buf, &mut self.state);
+            self.list = Some(list);
         }
 
         if area.height > 4 {
@@ -9,7 +9,7 @@ use xash3d_ratatui::XashBackend;
 
 use crate::{
     input::KeyEvent,
-    ui::{Screen, utils},
+    ui::{Screen, theme::theme, utils},
     widgets::{List, SelectResult, Value, WidgetMut},
 };
 
@@ -28,7 +28,6 @@ impl ListPopup {
         let title = title.to_compact_string();
 
         let mut list = List::new_first(items);
-        list.set_style(Style::default().black().on_gray());
         list.set_highlight_style(Style::new().add_modifier(Modifier::BOLD).white().on_black());
 
         let content_width = list
@@ -59,6 +58,8 @@ impl WidgetMut<SelectResult> for ListPopup {
             buf[pos].reset();
         }
         block.render(area, buf);
+        // the theme could be changed while the popup exists
+        self.list.set_style(theme().popup);
         self.list.render(inner_area, buf, screen);
     }
 
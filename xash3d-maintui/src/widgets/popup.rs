@@ -8,7 +8,7 @@ use xash3d_ratatui::XashBackend;
 
 use crate::{
     input::{Key, KeyEvent},
-    ui::{Screen, State, utils},
+    ui::{Screen, State, animation::PopupAnimation, bidi, utils},
     widgets::{Button, ConfirmResult, WidgetMut},
 };
 
@@ -26,6 +26,7 @@ pub struct ConfirmPopup {
     title: CompactString,
     content: CompactString,
     content_width: u16,
+    animation: PopupAnimation,
 }
 
 impl ConfirmPopup {
@@ -37,16 +38,20 @@ impl ConfirmPopup {
             title: title.to_compact_string(),
             content: content.to_compact_string(),
             content_width: content.width() as u16,
+            animation: PopupAnimation::default(),
         }
     }
 
     pub fn new(content: &str) -> Self {
         Self::with_title("Y/N", content)
     }
-}
 
-impl WidgetMut<ConfirmResult> for ConfirmPopup {
-    fn render(&mut self, area: Rect, buf: &mut Buffer, _: &Screen) {
+    /// Returns `true` if the popup must be rendered after it was closed.
+    pub fn is_closing(&self, time: f32) -> bool {
+        self.animation.is_closing(time)
+    }
+
+    fn render_popup(&mut self, area: Rect, buf: &mut Buffer) {
         let width = 2 + self.content_width;
         let area = utils::centered_rect_fixed(width, 4, area);
 
@@ -61,7 +66,7 @@ impl WidgetMut<ConfirmResult> for ConfirmPopup {
         let [text_area, buttons_area] =
             Layout::vertical([Constraint::Length(2), Constraint::Length(1)]).areas(inner_area);
 
-        let t = Text::styled(&*self.content, Style::new().red().on_gray());
+        let t = Text::styled(bidi::reorder(&self.content), Style::new().red().on_gray());
         let p = Paragraph::new(t).wrap(Wrap { trim: true });
         p.render(text_area, buf);
 
@@ -74,6 +79,14 @@ impl WidgetMut<ConfirmResult> for ConfirmPopup {
             .render(cancel_area, buf, *focus == Focus::Cancel);
         self.yes.render(yes_area, buf, *focus == Focus::Yes);
     }
+}
+
+impl WidgetMut<ConfirmResult> for ConfirmPopup {
+    fn render(&mut self, area: Rect, buf: &mut Buffer, screen: &Screen) {
+        let mut animation = self.animation;
+        animation.render(buf, screen.time, |buf| self.render_popup(area, buf));
+        self.animation = animation;
+    }
 
     fn key_event(&mut self, backend: &XashBackend, event: KeyEvent) -> ConfirmResult {
         let mut ret = ConfirmResult::None;
@@ -109,6 +122,7 @@ impl WidgetMut<ConfirmResult> for ConfirmPopup {
         }
         if ret != ConfirmResult::None {
             self.state.reset();
+            self.animation.close(backend.time());
         }
         ret
     }
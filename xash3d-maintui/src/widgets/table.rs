@@ -11,6 +11,7 @@ use crate::{
     input::{Key, KeyEvent},
     ui::{
         symbols,
+        theme::theme,
         utils::{self, Scroll},
     },
 };
@@ -79,7 +80,7 @@ impl<T> MyTable<T> {
         f: impl FnMut(&T) -> Option<Row>,
     ) {
         let style = if focused {
-            Style::new().black().on_yellow()
+            theme().highlight
         } else {
             Style::new().on_dark_gray()
         };
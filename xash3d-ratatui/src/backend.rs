@@ -1,37 +1,87 @@
-use alloc::vec::Vec;
+use ab_glyph::FontArc;
+use core::{ffi::CStr, mem};
+
+use alloc::{string::String, vec::Vec};
 use ratatui::{
     buffer::Cell,
     layout::{Position, Size},
     prelude::*,
 };
+use unicode_width::UnicodeWidthStr;
 use xash3d_ui::{
     color::RGBA,
-    misc::{Point as UiPoint, Rect as UiRect, Size as UiSize},
-    picture::Picture,
+    cvar::CVarFlags,
+    misc::{Rect as UiRect, Size as UiSize},
     prelude::*,
 };
 
-use crate::font::{Font, FontMap};
+use crate::{
+    background::Background,
+    cursor::{self, Cursor, CursorShape},
+    font::{self, AtlasStats, Font, FontFile, FontMap, GlyphInfo},
+    layer::{self, Layer},
+    palette::Palette,
+};
 
 const DEFAULT_FONT_SIZE: u16 = 21;
 
-const XASH_LOGO: &[u8] = include_bytes!("../data/xash_logo.png");
-
-fn scale_font_size(size: u16, width: u32, _height: u32) -> u16 {
-    let scale = if width > 1920 {
-        2.0
-    } else if width >= 720 {
-        1.375
+pub const MIN_FONT_SIZE: u16 = 8;
+pub const MAX_FONT_SIZE: u16 = 128;
+
+/// File name of the font in `resource/maintui_fonts`, the embedded font is used if empty.
+pub const UI_FONT: &CStr = c"ui_font";
+/// Font size in pixels, the size depends on the screen resolution if zero.
+pub const UI_FONT_SIZE: &CStr = c"ui_font_size";
+/// Scale of the font size, `auto` picks the scale from the screen size.
+pub const UI_SCALE: &CStr = c"ui_scale";
+/// Physical screen density in dots per inch for the `auto` scale, unknown if zero.
+pub const UI_DPI: &CStr = c"ui_dpi";
+/// Opacity of backgrounds if the menu is opened in game, from 0 to 1.
+pub const UI_BACKGROUND_ALPHA: &CStr = c"ui_background_alpha";
+
+/// Screen height in pixels where the automatic scale is 1.
+const REFERENCE_HEIGHT: f32 = 800.0;
+/// Screen density in dots per inch where the automatic scale is 1.
+const REFERENCE_DPI: f32 = 96.0;
+/// Smallest grid in cells the menus are designed for.
+const MIN_GRID: (f32, f32) = (80.0, 25.0);
+/// Largest grid in cells before the text becomes too small to read.
+const MAX_GRID: (f32, f32) = (160.0, 50.0);
+
+/// Returns the preferred font size for the physical screen density or for the screen
+/// height in pixels if the density is unknown.
+fn preferred_font_size(height: f32, dpi: f32) -> f32 {
+    if dpi > 0.0 {
+        DEFAULT_FONT_SIZE as f32 * dpi / REFERENCE_DPI
     } else {
-        return size;
-    };
-    (size as f32 * scale) as u16
+        DEFAULT_FONT_SIZE as f32 * height / REFERENCE_HEIGHT
+    }
+}
+
+/// Picks the font size for the screen size in pixels.
+///
+/// The preferred size is limited to keep the grid between [MIN_GRID] and [MAX_GRID].
+/// The minimum grid wins on small screens. `cell` is the size of the cell in pixels for
+/// the font size of one pixel.
+fn auto_font_size(width: f32, height: f32, preferred: f32, cell: (f32, f32)) -> f32 {
+    let (cell_width, cell_height) = cell;
+    let min = f32::max(
+        width / (cell_width * MAX_GRID.0),
+        height / (cell_height * MAX_GRID.1),
+    );
+    let max = f32::min(
+        width / (cell_width * MIN_GRID.0),
+        height / (cell_height * MIN_GRID.1),
+    );
+    preferred.max(min).min(max)
 }
 
 struct DrawCell {
     index: u16,
     x: i16,
     y: i16,
+    /// number of cells covered by the symbol
+    width: u8,
     fg: RGBA,
 }
 
@@ -44,9 +94,28 @@ pub struct XashBackend {
     mouse_pos: Position,
     cursor: Position,
     font_map: FontMap,
+    /// value of ui_font used for the current font
+    font_name: String,
+    embedded_font: FontArc,
+    /// cell size of the embedded font for the font size of one pixel
+    embedded_cell: (f32, f32),
+    fonts: Vec<FontFile>,
     // temporary buffer for sorted list of cells optimized for rendering
     cells: Vec<DrawCell>,
-    bg: Picture,
+    /// cells covered by wide symbols in the last drawn buffer
+    wide_tails: Vec<Position>,
+    palette: Palette,
+    /// the game is visible behind the menu
+    translucent: bool,
+    /// draw commands composited with the next drawn buffer
+    layers: Vec<Layer>,
+    background: Background,
+    /// time of the current frame in seconds
+    time: f32,
+    text_cursor: Cursor,
+    /// cells drawn with the ratatui backend interface
+    #[cfg(feature = "std")]
+    screen: Buffer,
 }
 
 impl XashBackend {
@@ -54,19 +123,72 @@ impl XashBackend {
         let globals = &engine.globals;
         let width = globals.screen_width();
         let height = globals.screen_height();
-        let font_size = scale_font_size(DEFAULT_FONT_SIZE, width, height);
-        Self {
+        engine.register_variable(UI_FONT, "", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_FONT_SIZE, "0", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_SCALE, "auto", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_DPI, "0", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_BACKGROUND_ALPHA, "1", CVarFlags::ARCHIVE);
+        let embedded_font = font::embedded_font();
+        let embedded_cell = {
+            // measure with a big size for better precision
+            const SIZE: u16 = 100;
+            let font = Font::new(SIZE as isize, &embedded_font, &[]);
+            let (w, h) = font.glyph_size();
+            (w as f32 / SIZE as f32, h as f32 / SIZE as f32)
+        };
+        let font = Font::new(DEFAULT_FONT_SIZE as isize, &embedded_font, &[]);
+        let mut backend = Self {
             engine,
             width,
             height,
             mouse_pos: Position::ORIGIN,
             cursor: Position::ORIGIN,
-            font_map: FontMap::new(engine, Font::new(font_size as isize)),
+            font_map: FontMap::new(engine, font),
+            font_name: String::new(),
+            embedded_font,
+            embedded_cell,
+            fonts: font::load_fonts(engine),
             cells: Vec::new(),
-            bg: engine
-                .pic_create(c"#mainui/backend/xash_logo.png", XASH_LOGO)
-                .unwrap(),
-        }
+            wide_tails: Vec::new(),
+            palette: Palette::default(),
+            translucent: false,
+            layers: Vec::new(),
+            background: Background::new(engine),
+            time: 0.0,
+            text_cursor: Cursor::new(engine),
+            #[cfg(feature = "std")]
+            screen: Buffer::default(),
+        };
+        let name = engine.get_cvar_string(UI_FONT).to_str().unwrap_or_default();
+        backend.font_name = name.into();
+        backend.reload_font();
+        backend
+    }
+
+    /// Returns the font size used if ui_font_size is zero.
+    ///
+    /// The size is scaled with ui_scale, in `auto` mode the grid size is estimated with
+    /// metrics of the embedded font.
+    fn default_font_size(&self) -> u16 {
+        let engine = self.engine;
+        let scale = engine.get_cvar_string(UI_SCALE);
+        let scale = scale.to_str().unwrap_or_default();
+        let size = match scale.parse::<f32>() {
+            Ok(scale) if scale > 0.0 => DEFAULT_FONT_SIZE as f32 * scale,
+            // auto or invalid value
+            _ => {
+                let width = self.width as f32;
+                let height = self.height as f32;
+                let preferred = preferred_font_size(height, engine.get_cvar_float(UI_DPI));
+                auto_font_size(width, height, preferred, self.embedded_cell)
+            }
+        };
+        (size as u16).clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+    }
+
+    /// Returns statistics of the glyph atlas.
+    pub fn atlas_stats(&self) -> AtlasStats {
+        self.font_map.stats()
     }
 
     pub fn cell_size_in_pixels(&self) -> Size {
@@ -102,10 +224,15 @@ impl XashBackend {
         if (0..self.height as u16).contains(&mouse.y) {
             cursor.y = mouse.y / cell.height;
         }
+        // the right half of a wide symbol belongs to the cell on the left
+        if self.is_wide_tail(cursor) {
+            cursor.x -= 1;
+        }
         cursor
     }
 
-    pub fn set_cursor_position(&mut self, mouse: Position) -> bool {
+    /// Moves the mouse pointer, returns `true` if the position was changed.
+    pub fn set_mouse_position(&mut self, mouse: Position) -> bool {
         if self.mouse_pos == mouse {
             return false;
         }
@@ -114,6 +241,12 @@ impl XashBackend {
         true
     }
 
+    fn is_wide_tail(&self, position: Position) -> bool {
+        self.wide_tails
+            .binary_search_by_key(&(position.y, position.x), |i| (i.y, i.x))
+            .is_ok()
+    }
+
     pub fn area_to_pixels(&self, area: Rect) -> Rect {
         let cell = self.cell_size_in_pixels();
         Rect::new(
@@ -147,6 +280,14 @@ impl XashBackend {
         None
     }
 
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
     pub fn decrease_font_size(&mut self) {
         self.set_font_size(self.get_font_size() - 1);
     }
@@ -159,72 +300,262 @@ impl XashBackend {
         self.font_map.font().size() as u16
     }
 
+    /// Changes the font size and saves it to ui_font_size.
     pub fn set_font_size(&mut self, size: u16) {
-        let size = size.clamp(8, 128) as isize;
-        if size != self.font_map.font().size() {
-            self.font_map = FontMap::new(self.engine, Font::new(size));
+        let size = size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+        self.engine.set_cvar_float(UI_FONT_SIZE, size as f32);
+        self.update_font();
+    }
+
+    fn font_size_from_cvar(&self) -> u16 {
+        let size = match self.engine.get_cvar_float(UI_FONT_SIZE) as u16 {
+            0 => self.default_font_size(),
+            size => size,
+        };
+        size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+    }
+
+    /// Reloads the font if ui_font, ui_font_size, ui_scale or ui_dpi was changed.
+    ///
+    /// Returns `true` if the font was changed.
+    pub fn update_font(&mut self) -> bool {
+        let engine = self.engine;
+        let name = engine.get_cvar_string(UI_FONT).to_str().unwrap_or_default();
+        let size = self.font_size_from_cvar();
+        if name == self.font_name && size == self.get_font_size() {
+            return false;
         }
+        self.font_name = name.into();
+        self.reload_font();
+        true
+    }
+
+    fn reload_font(&mut self) {
+        let size = self.font_size_from_cvar();
+        let name = self.font_name.as_str();
+        trace!("load font \"{name}\" with size {size}");
+        let main = self.fonts.iter().find(|i| i.name == name).map(|i| &i.font);
+        if main.is_none() && !name.is_empty() {
+            warn!("font \"{name}\" not found");
+        }
+        let mut fallback = Vec::new();
+        if main.is_some() {
+            fallback.push(self.embedded_font.clone());
+        }
+        fallback.extend(
+            self.fonts
+                .iter()
+                .filter(|i| i.name != name)
+                .map(|i| i.font.clone()),
+        );
+        let font = Font::new(
+            size as isize,
+            main.unwrap_or(&self.embedded_font),
+            &fallback,
+        );
+        self.font_map = FontMap::new(self.engine, font);
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
         self.width = width;
         self.height = height;
-        self.set_font_size(scale_font_size(DEFAULT_FONT_SIZE, self.width, self.height));
+        self.update_font();
+    }
+
+    /// Enables translucent backgrounds with ui_background_alpha.
+    pub fn set_translucent(&mut self, translucent: bool) {
+        self.translucent = translucent;
+    }
+
+    fn background_alpha(&self) -> u8 {
+        if !self.translucent {
+            return 255;
+        }
+        let alpha = self
+            .engine
+            .get_cvar_float(UI_BACKGROUND_ALPHA)
+            .clamp(0.0, 1.0);
+        (self.palette.alpha as f32 * alpha) as u8
     }
 
     pub fn draw_background(&mut self) {
         // fill screen with default color
         let size = UiSize::new(self.width, self.height);
-        self.engine.fill_rgba(color_bg(Color::Reset), size.into());
+        let [r, g, b] = self.palette.bg;
+        let alpha = self.background_alpha();
+        self.engine
+            .fill_rgba(RGBA::new(r, g, b, alpha), size.into());
+        if alpha != 255 {
+            // do not cover the game
+            return;
+        }
 
-        // draw xash logo at the right bottom corner
-        let size = self.bg.size();
+        // keep the logo one cell away from the screen edges
         let cell = self.cell_size_in_pixels();
         let screen = self.size();
-        let x = (screen.width.saturating_sub(1) * cell.width) as i32 - size.width as i32;
-        let y = (screen.height.saturating_sub(1) * cell.height) as i32 - size.height as i32;
-        let area = size.to_rect(UiPoint::new(x, y));
-        self.bg.draw_trans(RGBA::WHITE, area, None);
+        let logo_area = [
+            cell.width as i32,
+            cell.height as i32,
+            (screen.width.saturating_sub(1) * cell.width) as i32,
+            (screen.height.saturating_sub(1) * cell.height) as i32,
+        ];
+        let screen = UiSize::new(self.width, self.height);
+        self.background.draw(screen, logo_area);
+    }
+
+    /// Returns the time of the current frame in seconds.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Sets the time of the current frame in seconds, used for animations.
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+        self.text_cursor.set_time(time);
+    }
+
+    /// Shows the text cursor at the cell position or hides it if `None`.
+    pub fn set_text_cursor(&mut self, position: Option<Position>) {
+        if let Some(position) = position {
+            self.text_cursor.set_position(position);
+        }
+        self.text_cursor.set_visible(position.is_some());
+    }
+
+    /// Returns the position of the text cursor if it is visible.
+    pub fn text_cursor(&self) -> Option<Position> {
+        let cursor = &self.text_cursor;
+        cursor.is_visible().then(|| cursor.position())
+    }
+
+    /// Returns the position of the text cursor drawn by reversing cell colors.
+    fn block_cursor(&self) -> Option<Position> {
+        let cursor = &self.text_cursor;
+        (cursor.is_on() && cursor.shape() == CursorShape::Block).then(|| cursor.position())
+    }
+
+    fn draw_cursors(&self) {
+        let cell = self.cell_size_in_pixels();
+        if self.text_cursor.is_on() {
+            let [r, g, b] = self.palette.fg;
+            self.text_cursor.draw(cell, RGBA::rgb(r, g, b));
+        }
+        cursor::draw_mouse_pointer(self.engine, self.mouse_pos, cell);
+    }
+
+    /// Sets layers of draw commands for the next [draw_buffer](Self::draw_buffer).
+    pub fn set_layers(&mut self, layers: Vec<Layer>) {
+        self.layers = layers;
     }
 
     pub(crate) fn draw_buffer(&mut self, buffer: &Buffer) {
+        if buffer.area.width == 0 {
+            return;
+        }
+        let layers = mem::take(&mut self.layers);
+        let levels = layer::cell_levels(buffer, &layers);
+        self.wide_tails.clear();
+        self.font_map.begin_frame();
+        self.draw_cells(buffer, &levels, 0);
+        for (i, layer) in layers.into_iter().enumerate() {
+            layer.draw(self.engine);
+            let level = u8::try_from(i + 1).unwrap_or(u8::MAX);
+            self.draw_cells(buffer, &levels, level);
+        }
+        self.draw_cursors();
+    }
+
+    /// Draws cells placed above `level` layers.
+    fn draw_cells(&mut self, buffer: &Buffer, levels: &[u8], level: u8) {
         let cell_size = self.cell_size_in_pixels();
         let cell_width = cell_size.width as i32;
         let cell_height = cell_size.height as i32;
-        let width = self.width;
-        let mut x = 0;
-        let mut y = 0;
-        let iter = buffer.content().iter().enumerate().filter_map(|(i, cell)| {
-            // XXX: numas13: do we need multi-width characters?
-            let result = (!cell.skip && *cell != Cell::EMPTY).then_some((i as u16, x, y, cell));
-            x += cell_width;
-            if x + cell_width > width as i32 {
-                x = 0;
-                y += cell_height;
-            }
-            result
-        });
+        let area = buffer.area;
+        let columns = area.width as usize;
 
         // draw background and collect non-empty cells
+        let alpha = self.background_alpha();
         let ascent = self.font_map.font().ascent() as i32;
-        for (i, x, y, cell) in iter {
-            if cell.bg != Color::Reset {
-                let area = UiRect::new(x, y, cell_width as u32, cell_height as u32);
-                self.engine.fill_rgba(color_bg(cell.bg), area);
+        let block_cursor = self.block_cursor();
+        let mut cursor_cell;
+        let mut covered = 0;
+        for (i, mut cell) in buffer.content().iter().enumerate() {
+            let column = (i % columns) as u16;
+            let row = (i / columns) as u16;
+            let is_cursor = block_cursor == Some(Position::new(area.x + column, area.y + row));
+            if column == 0 {
+                covered = 0;
+            }
+            if covered > 0 {
+                // hidden by the wide symbol on the left
+                covered -= 1;
+                if level == 0 {
+                    self.wide_tails
+                        .push(Position::new(area.x + column, area.y + row));
+                }
+                continue;
+            }
+            let cell_level = levels.get(i).copied().unwrap_or(0);
+            if cell_level == 0 && (cell.skip || *cell == Cell::EMPTY) && !is_cursor {
+                continue;
+            }
+            if is_cursor {
+                cursor_cell = cell.clone();
+                cursor_cell.modifier.toggle(Modifier::REVERSED);
+                cell = &cursor_cell;
+            }
+
+            let symbol = cell.symbol();
+            let width = symbol.width().clamp(1, columns - column as usize) as u8;
+            covered = width - 1;
+            if cell_level != level {
+                continue;
+            }
+
+            let x = (area.x + column) as i32 * cell_width;
+            let y = (area.y + row) as i32 * cell_height;
+            let span = cell_width * width as i32;
+            let (fg, mut bg) = self.palette.cell_colors(cell, alpha);
+            if level > 0 && bg.is_none() {
+                // hide draw commands below the cell
+                let [r, g, b] = self.palette.bg;
+                bg = Some(RGBA::new(r, g, b, alpha));
+            }
+            if let Some(bg) = bg {
+                let area = UiRect::new(x, y, span as u32, cell_height as u32);
+                self.engine.fill_rgba(bg, area);
+            }
+            if cell.skip || cell.modifier.contains(Modifier::HIDDEN) {
+                continue;
             }
-            let y = y + ascent;
-            let fg = color_fg(cell.fg);
             if cell.modifier.contains(Modifier::UNDERLINED) {
-                let area = UiRect::new(x, y + 1, cell_width as u32, 2);
+                let area = UiRect::new(x, y + ascent + 1, span as u32, 2);
                 self.engine.fill_rgba(fg, area);
             }
-            if !cell.symbol().trim_start().is_empty() {
+            if cell.modifier.contains(Modifier::CROSSED_OUT) {
+                let area = UiRect::new(x, y + ascent * 2 / 3, span as u32, 2);
+                self.engine.fill_rgba(fg, area);
+            }
+            if !symbol.trim_start().is_empty() {
+                for c in symbol.chars() {
+                    self.font_map.cache(c, cell.modifier);
+                }
+                let index = i as u16;
                 let x = x as i16;
                 let y = y as i16;
-                self.cells.push(DrawCell { index: i, x, y, fg });
+                self.cells.push(DrawCell {
+                    index,
+                    x,
+                    y,
+                    width,
+                    fg,
+                });
             }
         }
 
+        // upload new glyphs before drawing
+        self.font_map.flush();
+
         // sorting by color results in a less state changes for better performance
         self.cells.sort_unstable_by_key(|i| i.fg);
 
@@ -232,53 +563,226 @@ impl XashBackend {
         for draw in self.cells.drain(..) {
             // SAFETY: index is from enumerate over buffer.content()
             let cell = unsafe { buffer.content().get_unchecked(draw.index as usize) };
+            let span = cell_width * draw.width as i32;
             for c in cell.symbol().chars() {
-                let (pic, info) = self.font_map.get(c, cell.modifier);
-                let area = UiRect::new(
-                    draw.x as i32 + info.bearing_x as i32,
-                    draw.y as i32 + info.bearing_y as i32,
-                    info.w as u32,
-                    info.h as u32,
-                );
-                let pic_area =
-                    UiRect::new(info.x as i32, info.y as i32, info.w as u32, info.h as u32);
+                let Some((pic, info)) = self.font_map.get(c, cell.modifier) else {
+                    continue;
+                };
+                let Some(((x, y, w, h), pic_area)) = fit_glyph(info, span, cell_height, ascent)
+                else {
+                    continue;
+                };
+                let area = UiRect::new(draw.x as i32 + x, draw.y as i32 + y, w, h);
                 pic.draw_trans(draw.fg, area, Some(pic_area));
             }
         }
     }
 }
 
-fn convert_color(color: Color, is_fg: bool) -> RGBA {
-    let color = match color {
-        Color::Reset if is_fg => 0xf6f6ef,
-        Color::Reset => 0x1a1a1a,
-        Color::Black => 0x000000,
-        Color::Red => 0xf4005f,
-        Color::Green => 0x98e024,
-        Color::Yellow => 0xfa8419,
-        Color::Blue => 0x9d65ff,
-        Color::Magenta => 0xa4307f,
-        Color::Cyan => 0x58d1eb,
-        Color::Gray => 0xc4c5b5,
-        Color::DarkGray => 0x625e4c,
-        Color::LightRed => 0xa4305f,
-        Color::LightGreen => 0x98e024,
-        Color::LightYellow => 0xe0d561,
-        Color::LightBlue => 0x9d65ff,
-        Color::LightMagenta => 0xf4307f,
-        Color::LightCyan => 0x58d1eb,
-        Color::White => 0xf6f6ef,
-        Color::Rgb(r, g, b) => u32::from_be_bytes([0, r, g, b]),
-        Color::Indexed(_) => todo!(),
-    };
-    let [_, r, g, b] = color.to_be_bytes();
-    RGBA::rgb(r, g, b)
+/// Places the glyph into the cell area of `width`x`height` pixels.
+///
+/// Glyphs wider than the area are scaled down, glyphs in wide cells are centered. The result
+/// is clipped to the cell area. Returns the position and size relative to the cell and the
+/// area in the glyph map.
+fn fit_glyph(
+    info: &GlyphInfo,
+    width: i32,
+    height: i32,
+    ascent: i32,
+) -> Option<((i32, i32, u32, u32), UiRect)> {
+    if info.w == 0 || info.h == 0 {
+        return None;
+    }
+    let (src_w, src_h) = (info.w as i32, info.h as i32);
+    let (mut x, mut y) = (info.bearing_x as i32, ascent + info.bearing_y as i32);
+    let (mut w, mut h) = (src_w, src_h);
+    if w > width {
+        // keep the baseline while scaling
+        h = h * width / w;
+        y = ascent + info.bearing_y as i32 * width / w;
+        x = 0;
+        w = width;
+    } else if width > x + w && x >= 0 && width >= 2 * w {
+        x = (width - w) / 2;
+    }
+
+    // clip to the cell area and convert clipped pixels back to the glyph map scale
+    let left = (-x).max(0);
+    let top = (-y).max(0);
+    let right = (x + w - width).max(0);
+    let bottom = (y + h - height).max(0);
+    if left + right >= w || top + bottom >= h {
+        return None;
+    }
+    let area = (
+        x + left,
+        y + top,
+        (w - left - right) as u32,
+        (h - top - bottom) as u32,
+    );
+    let src_left = left * src_w / w;
+    let src_top = top * src_h / h;
+    let src_right = right * src_w / w;
+    let src_bottom = bottom * src_h / h;
+    let pic_area = UiRect::new(
+        info.x as i32 + src_left,
+        info.y as i32 + src_top,
+        (src_w - src_left - src_right) as u32,
+        (src_h - src_top - src_bottom) as u32,
+    );
+    Some((area, pic_area))
 }
 
-fn color_bg(color: Color) -> RGBA {
-    convert_color(color, false)
+#[cfg(feature = "std")]
+mod terminal_backend {
+    use std::io;
+
+    use ratatui::backend::{Backend, ClearType, WindowSize};
+
+    use super::*;
+
+    impl XashBackend {
+        /// Returns cells drawn with the ratatui backend interface.
+        pub fn screen(&self) -> &Buffer {
+            &self.screen
+        }
+
+        fn resize_screen(&mut self) {
+            let area = self.area();
+            if self.screen.area != area {
+                self.screen.resize(area);
+            }
+        }
+    }
+
+    /// Allows to use the backend with [ratatui::Terminal].
+    ///
+    /// The screen is redrawn on every [flush](Backend::flush), the terminal must be drawn
+    /// every frame.
+    impl Backend for XashBackend {
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.resize_screen();
+            for (x, y, cell) in content {
+                if let Some(dst) = self.screen.cell_mut((x, y)) {
+                    *dst = cell.clone();
+                }
+            }
+            Ok(())
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.text_cursor.set_visible(false);
+            Ok(())
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.text_cursor.set_visible(true);
+            Ok(())
+        }
+
+        fn get_cursor_position(&mut self) -> io::Result<Position> {
+            Ok(self.text_cursor.position())
+        }
+
+        fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+            self.text_cursor.set_position(position.into());
+            Ok(())
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.resize_screen();
+            self.screen.reset();
+            Ok(())
+        }
+
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+            self.resize_screen();
+            let area = self.screen.area;
+            if area.is_empty() {
+                return Ok(());
+            }
+            let cursor = self.text_cursor.position();
+            let x = cursor.x.min(area.right() - 1);
+            let y = cursor.y.min(area.bottom() - 1);
+            let start = self.screen.index_of(x, y);
+            let line_start = self.screen.index_of(area.x, y);
+            let line_end = line_start + area.width as usize;
+            let range = match clear_type {
+                ClearType::All => 0..self.screen.content.len(),
+                ClearType::AfterCursor => start..self.screen.content.len(),
+                ClearType::BeforeCursor => 0..start,
+                ClearType::CurrentLine => line_start..line_end,
+                ClearType::UntilNewLine => start..line_end,
+            };
+            for cell in &mut self.screen.content[range] {
+                cell.reset();
+            }
+            Ok(())
+        }
+
+        fn size(&self) -> io::Result<Size> {
+            Ok(XashBackend::size(self))
+        }
+
+        fn window_size(&mut self) -> io::Result<WindowSize> {
+            Ok(WindowSize {
+                columns_rows: XashBackend::size(self),
+                pixels: Size::new(self.width as u16, self.height as u16),
+            })
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.resize_screen();
+            self.draw_background();
+            let screen = mem::take(&mut self.screen);
+            self.draw_buffer(&screen);
+            self.screen = screen;
+            Ok(())
+        }
+    }
 }
 
-fn color_fg(color: Color) -> RGBA {
-    convert_color(color, true)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_font_size_grid() {
+        let cell = (0.5, 1.2);
+        let screens = [
+            (640.0, 480.0),
+            (1920.0, 1080.0),
+            (2560.0, 1080.0),
+            (3840.0, 2160.0),
+        ];
+        for (width, height) in screens {
+            let preferred = preferred_font_size(height, 0.0);
+            let size = auto_font_size(width, height, preferred, cell);
+            let columns = width / (size * cell.0);
+            let rows = height / (size * cell.1);
+            let (columns, rows) = (columns.round(), rows.round());
+            assert!(
+                (MIN_GRID.0..=MAX_GRID.0).contains(&columns)
+                    && (MIN_GRID.1..=MAX_GRID.1).contains(&rows),
+                "{width}x{height} has grid {columns}x{rows}"
+            );
+        }
+    }
+
+    #[test]
+    fn auto_font_size_dpi() {
+        let cell = (0.5, 1.2);
+        let (width, height) = (2560.0, 1600.0);
+        let normal = auto_font_size(width, height, preferred_font_size(height, 96.0), cell);
+        let dense = auto_font_size(width, height, preferred_font_size(height, 192.0), cell);
+        // the largest grid limits the size on a normal density screen
+        assert_eq!(normal, width / (cell.0 * MAX_GRID.0));
+        assert_eq!(dense, DEFAULT_FONT_SIZE as f32 * 2.0);
+        // the grid is still limited on high density screens with a small resolution
+        let size = auto_font_size(1280.0, 720.0, preferred_font_size(720.0, 192.0), cell);
+        assert_eq!(size, 720.0 / (cell.1 * MIN_GRID.1));
+    }
 }
@@ -0,0 +1,272 @@
+use core::{ffi::CStr, str::FromStr};
+
+use alloc::{ffi::CString, string::String, vec::Vec};
+use xash3d_ui::{
+    color::RGBA,
+    cvar::CVarFlags,
+    misc::{Point as UiPoint, Rect as UiRect, Size as UiSize},
+    parser::{TokenError, Tokens},
+    picture::Picture,
+    prelude::*,
+};
+
+/// Path to the background image, the mod background is used if empty.
+pub const UI_BACKGROUND: &CStr = c"ui_background";
+/// Scaling of the background image: `none`, `fit`, `fill` or `tile`.
+pub const UI_BACKGROUND_MODE: &CStr = c"ui_background_mode";
+/// Show the logo on top of the background.
+pub const UI_LOGO: &CStr = c"ui_logo";
+/// Placement of the logo: `top-left`, `top-right`, `bottom-left`, `bottom-right` or `center`.
+pub const UI_LOGO_POSITION: &CStr = c"ui_logo_position";
+
+const XASH_LOGO: &[u8] = include_bytes!("../data/xash_logo.png");
+const XASH_LOGO_PATH: &CStr = c"#mainui/backend/xash_logo.png";
+
+/// Background tiles used by the classic menu.
+const BACKGROUND_LAYOUT: &CStr = c"resource/BackgroundLayout.txt";
+const SPLASH: &str = "gfx/shell/splash.bmp";
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+enum Mode {
+    /// Do not draw the background image.
+    None,
+    /// Scale to fit the screen, keep the aspect ratio.
+    Fit,
+    /// Scale to cover the screen, keep the aspect ratio.
+    #[default]
+    Fill,
+    /// Repeat without scaling.
+    Tile,
+}
+
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "fit" => Ok(Self::Fit),
+            "fill" => Ok(Self::Fill),
+            "tile" => Ok(Self::Tile),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+enum Position {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+    Center,
+}
+
+impl FromStr for Position {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top-left" => Ok(Self::TopLeft),
+            "top-right" => Ok(Self::TopRight),
+            "bottom-left" => Ok(Self::BottomLeft),
+            "bottom-right" => Ok(Self::BottomRight),
+            "center" => Ok(Self::Center),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Part of the background image.
+struct Tile {
+    path: CString,
+    pic: Picture,
+    /// Position in the background image.
+    x: i32,
+    y: i32,
+}
+
+pub(crate) struct Background {
+    engine: UiEngineRef,
+    /// Value of ui_background used for loaded tiles.
+    source: Option<String>,
+    /// Size of the background image in pixels.
+    size: UiSize,
+    tiles: Vec<Tile>,
+    logo: Picture,
+}
+
+impl Background {
+    pub(crate) fn new(engine: UiEngineRef) -> Self {
+        engine.register_variable(UI_BACKGROUND, "", CVarFlags::ARCHIVE);
+        // the mod background is drawn only if the game has one
+        engine.register_variable(UI_BACKGROUND_MODE, "fill", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_LOGO, "1", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_LOGO_POSITION, "bottom-right", CVarFlags::ARCHIVE);
+        Self {
+            engine,
+            source: None,
+            size: UiSize::new(0, 0),
+            tiles: Vec::new(),
+            logo: engine.pic_create(XASH_LOGO_PATH, XASH_LOGO).unwrap(),
+        }
+    }
+
+    fn free_tiles(&mut self) {
+        for tile in self.tiles.drain(..) {
+            self.engine.pic_free(&tile.path);
+        }
+        self.size = UiSize::new(0, 0);
+    }
+
+    fn add_tile(&mut self, path: &str, x: i32, y: i32) -> Option<UiSize> {
+        let path = CString::new(path).ok()?;
+        let pic = self.engine.pic_load(&path).ok()?;
+        let size = pic.size();
+        self.tiles.push(Tile { path, pic, x, y });
+        Some(size)
+    }
+
+    /// Loads tiles in the format of `resource/BackgroundLayout.txt`.
+    fn load_layout<'a>(&mut self, src: &'a str) -> Result<(), TokenError<'a>> {
+        let mut tokens = Tokens::new(src);
+        tokens.expect("resolution")?;
+        let width = tokens.parse()?;
+        let height = tokens.parse()?;
+        self.size = UiSize::new(width, height);
+        while let Some(path) = tokens.next() {
+            let path = path?;
+            let _scaled: &str = tokens.parse()?;
+            let x = tokens.parse()?;
+            let y = tokens.parse()?;
+            if self.add_tile(path, x, y).is_none() {
+                warn!("failed to load background tile \"{path}\"");
+            }
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, source: &str) {
+        self.free_tiles();
+        self.source = Some(source.into());
+        if !source.is_empty() {
+            match self.add_tile(source, 0, 0) {
+                Some(size) => self.size = size,
+                None => warn!("failed to load background \"{source}\""),
+            }
+            return;
+        }
+
+        let engine = self.engine;
+        if let Ok(file) = engine.load_file(BACKGROUND_LAYOUT) {
+            let src = file.as_str().unwrap_or_default();
+            match self.load_layout(src) {
+                Ok(()) if !self.tiles.is_empty() => return,
+                Ok(()) => {}
+                Err(err) => error!("failed to parse {BACKGROUND_LAYOUT:?}, {err:?}"),
+            }
+            self.free_tiles();
+        }
+        if let Some(size) = self.add_tile(SPLASH, 0, 0) {
+            self.size = size;
+        }
+    }
+
+    /// Reloads images if ui_background was changed.
+    fn update(&mut self) {
+        let source = self.engine.get_cvar_string(UI_BACKGROUND);
+        let source = source.to_str().unwrap_or_default();
+        if self.source.as_deref() != Some(source) {
+            trace!("load background \"{source}\"");
+            self.load(source);
+        }
+    }
+
+    fn draw_tiles(&self, x: i32, y: i32, scale: f32) {
+        for tile in &self.tiles {
+            let size = tile.pic.size();
+            let area = UiRect::new(
+                x + (tile.x as f32 * scale) as i32,
+                y + (tile.y as f32 * scale) as i32,
+                (size.width as f32 * scale).ceil() as u32,
+                (size.height as f32 * scale).ceil() as u32,
+            );
+            tile.pic.draw(RGBA::WHITE, area, None);
+        }
+    }
+
+    fn draw_image(&mut self, screen: UiSize) {
+        let mode = self.engine.get_cvar_string(UI_BACKGROUND_MODE);
+        let mode = mode.to_str().ok().and_then(|i| i.parse().ok());
+        let mode = mode.unwrap_or_default();
+        if mode == Mode::None {
+            if self.source.is_some() {
+                self.free_tiles();
+                self.source = None;
+            }
+            return;
+        }
+        self.update();
+        let size = self.size;
+        if self.tiles.is_empty() || size.width == 0 || size.height == 0 {
+            return;
+        }
+        let sx = screen.width as f32 / size.width as f32;
+        let sy = screen.height as f32 / size.height as f32;
+        let scale = match mode {
+            Mode::None => return,
+            Mode::Fit => sx.min(sy),
+            Mode::Fill => sx.max(sy),
+            Mode::Tile => {
+                for y in (0..screen.height).step_by(size.height as usize) {
+                    for x in (0..screen.width).step_by(size.width as usize) {
+                        self.draw_tiles(x as i32, y as i32, 1.0);
+                    }
+                }
+                return;
+            }
+        };
+        let x = (screen.width as f32 - size.width as f32 * scale) / 2.0;
+        let y = (screen.height as f32 - size.height as f32 * scale) / 2.0;
+        self.draw_tiles(x as i32, y as i32, scale);
+    }
+
+    fn draw_logo(&self, left: i32, top: i32, right: i32, bottom: i32) {
+        let engine = self.engine;
+        if engine.get_cvar_float(UI_LOGO) == 0.0 {
+            return;
+        }
+        let position = engine.get_cvar_string(UI_LOGO_POSITION);
+        let position = position.to_str().ok().and_then(|i| i.parse().ok());
+        let size = self.logo.size();
+        let right = right - size.width as i32;
+        let bottom = bottom - size.height as i32;
+        let (x, y) = match position.unwrap_or_default() {
+            Position::TopLeft => (left, top),
+            Position::TopRight => (right, top),
+            Position::BottomLeft => (left, bottom),
+            Position::BottomRight => (right, bottom),
+            Position::Center => ((left + right) / 2, (top + bottom) / 2),
+        };
+        let area = size.to_rect(UiPoint::new(x, y));
+        self.logo.draw_trans(RGBA::WHITE, area, None);
+    }
+
+    /// Draws the background image and the logo.
+    ///
+    /// The logo is placed inside of the `logo_area` with left, top, right and bottom edges
+    /// in pixels.
+    pub(crate) fn draw(&mut self, screen: UiSize, logo_area: [i32; 4]) {
+        self.draw_image(screen);
+        let [left, top, right, bottom] = logo_area;
+        self.draw_logo(left, top, right, bottom);
+    }
+}
+
+impl Drop for Background {
+    fn drop(&mut self) {
+        self.free_tiles();
+        self.engine.pic_free(XASH_LOGO_PATH);
+    }
+}
@@ -0,0 +1,152 @@
+use core::{ffi::CStr, str::FromStr};
+
+use ratatui::layout::{Position, Size};
+use xash3d_ui::{color::RGBA, cvar::CVarFlags, misc::Rect as UiRect, prelude::*};
+
+/// Shape of the text cursor: `block`, `bar` or `underline`.
+pub const UI_CURSOR_SHAPE: &CStr = c"ui_cursor_shape";
+/// Blink the text cursor.
+pub const UI_CURSOR_BLINK: &CStr = c"ui_cursor_blink";
+/// Draw the mouse pointer for platforms where the engine hides the system cursor.
+pub const UI_MOUSE_POINTER: &CStr = c"ui_mouse_pointer";
+
+/// Time in seconds the blinking cursor is visible or hidden.
+const BLINK_TIME: f32 = 0.5;
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub(crate) enum CursorShape {
+    /// Reverses colors of the cell.
+    #[default]
+    Block,
+    /// Vertical line on the left side of the cell.
+    Bar,
+    /// Horizontal line at the bottom of the cell.
+    Underline,
+}
+
+impl FromStr for CursorShape {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "block" => Ok(Self::Block),
+            "bar" => Ok(Self::Bar),
+            "underline" => Ok(Self::Underline),
+            _ => Err(()),
+        }
+    }
+}
+
+pub(crate) struct Cursor {
+    engine: UiEngineRef,
+    position: Position,
+    visible: bool,
+    /// Time of the last redraw.
+    time: f32,
+    /// Time when the cursor was moved, the blinking starts from the visible state.
+    moved: f32,
+}
+
+impl Cursor {
+    pub(crate) fn new(engine: UiEngineRef) -> Self {
+        engine.register_variable(UI_CURSOR_SHAPE, "block", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_CURSOR_BLINK, "1", CVarFlags::ARCHIVE);
+        engine.register_variable(UI_MOUSE_POINTER, "0", CVarFlags::ARCHIVE);
+        Self {
+            engine,
+            position: Position::ORIGIN,
+            visible: false,
+            time: 0.0,
+            moved: 0.0,
+        }
+    }
+
+    pub(crate) fn position(&self) -> Position {
+        self.position
+    }
+
+    pub(crate) fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub(crate) fn set_position(&mut self, position: Position) {
+        if self.position != position {
+            self.position = position;
+            self.moved = self.time;
+        }
+    }
+
+    pub(crate) fn set_visible(&mut self, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.moved = self.time;
+        }
+    }
+
+    pub(crate) fn set_time(&mut self, time: f32) {
+        self.time = time;
+    }
+
+    pub(crate) fn shape(&self) -> CursorShape {
+        let shape = self.engine.get_cvar_string(UI_CURSOR_SHAPE);
+        shape
+            .to_str()
+            .ok()
+            .and_then(|i| i.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the cursor must be drawn in the current frame.
+    pub(crate) fn is_on(&self) -> bool {
+        if !self.visible {
+            return false;
+        }
+        if self.engine.get_cvar_float(UI_CURSOR_BLINK) == 0.0 {
+            return true;
+        }
+        let phase = ((self.time - self.moved).max(0.0) / BLINK_TIME) as u32;
+        phase % 2 == 0
+    }
+
+    /// Draws bar and underline shapes, the block is drawn with cells.
+    pub(crate) fn draw(&self, cell: Size, color: RGBA) {
+        let x = (self.position.x * cell.width) as i32;
+        let y = (self.position.y * cell.height) as i32;
+        let (w, h) = (cell.width as u32, cell.height as u32);
+        let thickness = (h / 10).max(2);
+        let area = match self.shape() {
+            CursorShape::Block => return,
+            CursorShape::Bar => UiRect::new(x, y, thickness, h),
+            CursorShape::Underline => UiRect::new(x, y + (h - thickness) as i32, w, thickness),
+        };
+        self.engine.fill_rgba(color, area);
+    }
+}
+
+/// Draws an arrow with the tip at the mouse position.
+pub(crate) fn draw_mouse_pointer(engine: UiEngineRef, mouse: Position, cell: Size) {
+    if engine.get_cvar_float(UI_MOUSE_POINTER) == 0.0 {
+        return;
+    }
+    let (x, y) = (mouse.x as i32, mouse.y as i32);
+    let height = cell.height.max(8) as i32;
+    // the arrow gets wider to the middle and narrower to the bottom
+    let row_width = |row: i32| {
+        let width = if row < height * 3 / 4 {
+            row / 2 + 1
+        } else {
+            (height - row) / 2 + 1
+        };
+        width as u32
+    };
+    // draw the outline first to not cover the arrow
+    let outline = RGBA::rgb(0, 0, 0);
+    for row in 0..height {
+        let area = UiRect::new(x - 1, y + row - 1, row_width(row) + 2, 3);
+        engine.fill_rgba(outline, area);
+    }
+    for row in 0..height {
+        let area = UiRect::new(x, y + row, row_width(row), 1);
+        engine.fill_rgba(RGBA::WHITE, area);
+    }
+}
@@ -0,0 +1,233 @@
+use core::{fmt::Write, str::FromStr};
+
+use alloc::string::String;
+use ratatui::{buffer::Buffer, style::Modifier};
+use unicode_width::UnicodeWidthStr;
+
+use crate::palette::Palette;
+
+/// Modifiers exported as styles, colors already include other modifiers.
+const STYLE_MODIFIERS: Modifier = Modifier::BOLD
+    .union(Modifier::ITALIC)
+    .union(Modifier::UNDERLINED)
+    .union(Modifier::CROSSED_OUT)
+    .union(Modifier::HIDDEN);
+
+/// Format of the screen dump.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum DumpFormat {
+    /// Plain text without colors.
+    #[default]
+    Text,
+    /// Text with ANSI escape sequences.
+    Ansi,
+    /// HTML page.
+    Html,
+}
+
+impl DumpFormat {
+    /// Returns the default file extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Ansi => "ans",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "ansi" => Ok(Self::Ansi),
+            "html" => Ok(Self::Html),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Style of a run of cells.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Style {
+    fg: [u8; 3],
+    bg: [u8; 3],
+    modifier: Modifier,
+}
+
+/// Calls `f` for every run of cells with the same style and `None` at the end of a line.
+fn for_each_run(buffer: &Buffer, palette: &Palette, mut f: impl FnMut(Option<(Style, &str)>)) {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut style = None;
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                // hidden by the wide symbol on the left
+                covered -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            let symbol = if cell.skip { " " } else { cell.symbol() };
+            covered = symbol.width().saturating_sub(1);
+            let (fg, bg, _) = palette.cell_rgb(cell);
+            let current = Style {
+                fg,
+                bg,
+                modifier: cell.modifier & STYLE_MODIFIERS,
+            };
+            if style.is_some_and(|style| style != current) {
+                f(style.map(|style| (style, text.as_str())));
+                text.clear();
+            }
+            style = Some(current);
+            text.push_str(symbol);
+        }
+        if let Some(style) = style {
+            f(Some((style, text.as_str())));
+            text.clear();
+        }
+        f(None);
+    }
+}
+
+fn dump_text(buffer: &Buffer, out: &mut String) {
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let start = out.len();
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            let symbol = if cell.skip { " " } else { cell.symbol() };
+            covered = symbol.width().saturating_sub(1);
+            out.push_str(symbol);
+        }
+        out.truncate(start + out[start..].trim_end().len());
+        out.push('\n');
+    }
+}
+
+fn dump_ansi(buffer: &Buffer, palette: &Palette, out: &mut String) {
+    for_each_run(buffer, palette, |run| {
+        let Some((style, text)) = run else {
+            out.push_str("\x1b[0m\n");
+            return;
+        };
+        let [r, g, b] = style.fg;
+        write!(out, "\x1b[0;38;2;{r};{g};{b}").unwrap();
+        let [r, g, b] = style.bg;
+        write!(out, ";48;2;{r};{g};{b}").unwrap();
+        for (modifier, code) in [
+            (Modifier::BOLD, 1),
+            (Modifier::ITALIC, 3),
+            (Modifier::UNDERLINED, 4),
+            (Modifier::HIDDEN, 8),
+            (Modifier::CROSSED_OUT, 9),
+        ] {
+            if style.modifier.contains(modifier) {
+                write!(out, ";{code}").unwrap();
+            }
+        }
+        out.push('m');
+        out.push_str(text);
+    });
+}
+
+fn push_html_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn dump_html(buffer: &Buffer, palette: &Palette, out: &mut String) {
+    let [r, g, b] = palette.bg;
+    let bg = format!("#{r:02x}{g:02x}{b:02x}");
+    let [r, g, b] = palette.fg;
+    let fg = format!("#{r:02x}{g:02x}{b:02x}");
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Xash3D TUI</title>\n<style>\n");
+    writeln!(out, "body {{ background: {bg}; color: {fg}; }}").unwrap();
+    out.push_str("pre { font-family: monospace; line-height: 1.2; }\n");
+    out.push_str("</style>\n</head>\n<body>\n<pre>");
+    for_each_run(buffer, palette, |run| {
+        let Some((style, text)) = run else {
+            out.push('\n');
+            return;
+        };
+        let [r, g, b] = if style.modifier.contains(Modifier::HIDDEN) {
+            style.bg
+        } else {
+            style.fg
+        };
+        write!(out, "<span style=\"color: #{r:02x}{g:02x}{b:02x}").unwrap();
+        let [r, g, b] = style.bg;
+        write!(out, "; background: #{r:02x}{g:02x}{b:02x}").unwrap();
+        if style.modifier.contains(Modifier::BOLD) {
+            out.push_str("; font-weight: bold");
+        }
+        if style.modifier.contains(Modifier::ITALIC) {
+            out.push_str("; font-style: italic");
+        }
+        let underlined = style.modifier.contains(Modifier::UNDERLINED);
+        let crossed_out = style.modifier.contains(Modifier::CROSSED_OUT);
+        match (underlined, crossed_out) {
+            (true, true) => out.push_str("; text-decoration: underline line-through"),
+            (true, false) => out.push_str("; text-decoration: underline"),
+            (false, true) => out.push_str("; text-decoration: line-through"),
+            (false, false) => {}
+        }
+        out.push_str("\">");
+        push_html_escaped(out, text);
+        out.push_str("</span>");
+    });
+    out.push_str("</pre>\n</body>\n</html>\n");
+}
+
+/// Serializes the buffer with colors from the palette.
+pub fn dump(buffer: &Buffer, palette: &Palette, format: DumpFormat) -> String {
+    let mut out = String::new();
+    match format {
+        DumpFormat::Text => dump_text(buffer, &mut out),
+        DumpFormat::Ansi => dump_ansi(buffer, palette, &mut out),
+        DumpFormat::Html => dump_html(buffer, palette, &mut out),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Rect, style::Stylize, text::Line, widgets::Widget};
+
+    use super::*;
+
+    fn buffer() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+        Line::from(vec!["a<b".bold(), " 测".into()]).render(buffer.area, &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn text() {
+        let out = dump(&buffer(), &Palette::default(), DumpFormat::Text);
+        assert_eq!(out, "a<b 测\n\n");
+    }
+
+    #[test]
+    fn html() {
+        let out = dump(&buffer(), &Palette::default(), DumpFormat::Html);
+        assert!(out.contains("font-weight: bold\">a&lt;b</span>"));
+        assert!(out.contains("\"> 测  </span>"));
+    }
+}
@@ -1,5 +1,7 @@
-use ab_glyph::{Font as _, FontRef, PxScaleFont, ScaleFont};
-use alloc::{boxed::Box, ffi::CString, vec::Vec};
+use core::{ffi::CStr, iter};
+
+use ab_glyph::{Font as _, FontArc, OutlinedGlyph, PxScaleFont, ScaleFont};
+use alloc::{collections::BTreeMap, ffi::CString, string::String, vec::Vec};
 use ratatui::style::Modifier;
 use xash3d_ui::{picture::Picture, prelude::*};
 
@@ -7,16 +9,85 @@ use crate::bmp::{Bmp, Components};
 
 const FONT: &[u8] = include_bytes!("../fonts/DepartureMono-1.422/DepartureMono-Regular.otf");
 
+const FONTS_DIR: &str = "resource/maintui_fonts";
+const FONTS_PATH: &CStr = c"resource/maintui_fonts/*.*";
+
+pub fn embedded_font() -> FontArc {
+    FontArc::try_from_slice(FONT).unwrap()
+}
+
+/// Returns file names of fonts in the game filesystem sorted by name.
+pub fn font_names(engine: UiEngineRef) -> Vec<String> {
+    let files = engine.get_files_list(FONTS_PATH, false);
+    let mut names: Vec<String> = files
+        .iter()
+        .filter_map(|path| {
+            let path = path.to_str().ok()?;
+            let name = path.rsplit('/').next()?;
+            let (_, ext) = name.rsplit_once('.')?;
+            let is_font = ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf");
+            is_font.then(|| name.into())
+        })
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+pub struct FontFile {
+    pub name: String,
+    pub font: FontArc,
+}
+
+/// Loads fonts from the game filesystem.
+pub fn load_fonts(engine: UiEngineRef) -> Vec<FontFile> {
+    let mut fonts = Vec::new();
+    for name in font_names(engine) {
+        let path = CString::new(format!("{FONTS_DIR}/{name}")).unwrap();
+        let Ok(file) = engine.load_file(path.as_c_str()) else {
+            error!("failed to open {path:?}");
+            continue;
+        };
+        match FontArc::try_from_vec(file.as_bytes().to_vec()) {
+            Ok(font) => {
+                debug!("loaded font {path:?}");
+                fonts.push(FontFile { name, font });
+            }
+            Err(err) => error!("failed to load font {path:?}, {err}"),
+        }
+    }
+    fonts
+}
+
 pub struct Font {
-    font: PxScaleFont<FontRef<'static>>,
+    /// Defines the cell size.
+    font: PxScaleFont<FontArc>,
+    /// Used for glyphs missing in the main font.
+    fallback: Vec<PxScaleFont<FontArc>>,
 }
 
 impl Font {
-    pub fn new(size: isize) -> Self {
+    pub fn new(size: isize, font: &FontArc, fallback: &[FontArc]) -> Self {
+        let size = size as f32;
         Self {
-            font: FontRef::try_from_slice(FONT)
-                .unwrap()
-                .into_scaled(size as f32),
+            font: font.clone().into_scaled(size),
+            fallback: fallback
+                .iter()
+                .map(|i| i.clone().into_scaled(size))
+                .collect(),
+        }
+    }
+
+    /// Returns the outline of the glyph from the first font that has it.
+    ///
+    /// Missing glyphs are replaced with a white square.
+    fn outline_glyph(&self, c: char) -> Option<OutlinedGlyph> {
+        let font = iter::once(&self.font)
+            .chain(&self.fallback)
+            .find(|font| font.glyph_id(c).0 != 0);
+        match font {
+            Some(font) => font.outline_glyph(font.scaled_glyph(c)),
+            None => self.font.outline_glyph(self.font.scaled_glyph('\u{25a1}')),
         }
     }
 
@@ -45,129 +116,339 @@ pub struct GlyphInfo {
     pub bearing_y: i16,
 }
 
-impl GlyphInfo {
-    fn new(x: u16, y: u16, bounds: &ab_glyph::Rect) -> Self {
-        GlyphInfo {
-            x,
-            y,
-            w: bounds.width() as u16,
-            h: bounds.height() as u16,
-            bearing_x: bounds.min.x as i16,
+/// Glyph variant generated for ratatui modifiers.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct GlyphStyle {
+    bold: bool,
+    italic: bool,
+}
+
+impl GlyphStyle {
+    /// Horizontal shift per pixel of height for synthetic italic.
+    const SHEAR: f32 = 0.2;
+}
+
+impl From<Modifier> for GlyphStyle {
+    fn from(modifier: Modifier) -> Self {
+        Self {
+            bold: modifier.contains(Modifier::BOLD),
+            italic: modifier.contains(Modifier::ITALIC),
+        }
+    }
+}
+
+/// Rasterized glyph with applied style.
+struct Glyph {
+    w: u16,
+    h: u16,
+    bearing_x: i16,
+    bearing_y: i16,
+    coverage: Vec<u8>,
+}
+
+impl Glyph {
+    fn new(outline: &OutlinedGlyph, style: GlyphStyle) -> Self {
+        let bounds = outline.px_bounds();
+        let (w, h) = (bounds.width() as i32, bounds.height() as i32);
+        // rows above the baseline
+        let ascent = -bounds.min.y as i32;
+        let shear = |py: i32| {
+            if style.italic {
+                ((ascent - py) as f32 * GlyphStyle::SHEAR) as i32
+            } else {
+                0
+            }
+        };
+        let shift = shear(h - 1).min(0);
+        let bold = style.bold as i32;
+        let width = w + shear(0) - shift + bold;
+        let mut coverage = vec![0; (width * h) as usize];
+        outline.draw(|px, py, f| {
+            if f <= 0.0 {
+                return;
+            }
+            let a = (f * 255.0) as u8;
+            let y = py as i32;
+            let x = px as i32 + shear(y) - shift;
+            // synthetic emboldening by one pixel dilation
+            for x in x..=x + bold {
+                let i = (y * width + x) as usize;
+                coverage[i] = coverage[i].max(a);
+            }
+        });
+        Self {
+            w: width as u16,
+            h: h as u16,
+            bearing_x: bounds.min.x as i16 + shift as i16,
             bearing_y: bounds.min.y as i16,
+            coverage,
         }
     }
 }
 
-struct GlyphMap {
+/// Glyph atlas statistics for debugging.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct AtlasStats {
+    /// The number of textures.
+    pub pages: usize,
+    /// The number of cached glyphs.
+    pub glyphs: usize,
+    /// Pixels occupied by cached glyphs.
+    pub used: u32,
+    /// Pixels in all textures.
+    pub capacity: u32,
+    /// Glyphs found in the cache.
+    pub hits: u64,
+    /// Glyphs rasterized on demand.
+    pub misses: u64,
+    /// Glyphs removed to free space.
+    pub evictions: u64,
+    /// Textures uploaded to the engine.
+    pub uploads: u64,
+}
+
+type GlyphKey = (GlyphStyle, char);
+
+struct CachedGlyph {
+    info: GlyphInfo,
+    /// Shelf index, empty glyphs and glyphs that did not fit do not take space in
+    /// the atlas.
+    shelf: Option<usize>,
+}
+
+/// Row of glyphs with the same maximum height.
+struct Shelf {
+    page: usize,
+    y: u16,
+    height: u16,
+    /// Offset of the free space.
+    x: u16,
+    /// Frame when a glyph from the shelf was used the last time.
+    last_used: u32,
+    glyphs: Vec<GlyphKey>,
+}
+
+/// Packs glyphs into shelves of fixed size pages.
+struct Packer {
+    page_size: u16,
+    /// Minimum height of new shelves.
+    shelf_height: u16,
+    /// Used height of every page.
+    pages: Vec<u16>,
+    shelves: Vec<Shelf>,
+}
+
+impl Packer {
+    const MAX_PAGES: usize = 4;
+
+    fn new(page_size: u16, shelf_height: u16) -> Self {
+        Self {
+            page_size,
+            shelf_height,
+            pages: Vec::new(),
+            shelves: Vec::new(),
+        }
+    }
+
+    /// Marks the shelf as used in the frame.
+    fn touch(&mut self, shelf: usize, frame: u32) {
+        self.shelves[shelf].last_used = frame;
+    }
+
+    /// Finds space for the glyph, returns the shelf index and the offset in the shelf.
+    ///
+    /// Keys of glyphs removed from an evicted shelf are appended to `evicted`.
+    fn alloc(
+        &mut self,
+        key: GlyphKey,
+        w: u16,
+        h: u16,
+        frame: u32,
+        evicted: &mut Vec<GlyphKey>,
+    ) -> Option<(usize, u16)> {
+        let size = self.page_size;
+        if w > size || h > size {
+            return None;
+        }
+        let free = self
+            .shelves
+            .iter()
+            .position(|s| h <= s.height && s.x + w <= size);
+        let index = match free {
+            Some(index) => index,
+            None => match self.add_shelf(h) {
+                Some(index) => index,
+                None => self.evict(h, frame, evicted)?,
+            },
+        };
+        let shelf = &mut self.shelves[index];
+        let x = shelf.x;
+        shelf.x += w;
+        shelf.last_used = frame;
+        shelf.glyphs.push(key);
+        Some((index, x))
+    }
+
+    fn add_shelf(&mut self, h: u16) -> Option<usize> {
+        let size = self.page_size;
+        // shelves have the cell height to be reusable by most glyphs
+        let height = h.max(self.shelf_height).min(size);
+        let page = match self.pages.iter().position(|&used| size - used >= height) {
+            Some(page) => page,
+            None if self.pages.len() < Self::MAX_PAGES => {
+                trace!("create glyph atlas page {} {size}x{size}", self.pages.len());
+                self.pages.push(0);
+                self.pages.len() - 1
+            }
+            None => return None,
+        };
+        let y = self.pages[page];
+        self.pages[page] += height;
+        self.shelves.push(Shelf {
+            page,
+            y,
+            height,
+            x: 0,
+            last_used: 0,
+            glyphs: Vec::new(),
+        });
+        Some(self.shelves.len() - 1)
+    }
+
+    /// Clears the least recently used shelf not used in the current frame.
+    fn evict(&mut self, h: u16, frame: u32, evicted: &mut Vec<GlyphKey>) -> Option<usize> {
+        let (index, shelf) = self
+            .shelves
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, shelf)| shelf.last_used != frame && h <= shelf.height)
+            .max_by_key(|(_, shelf)| frame.wrapping_sub(shelf.last_used))?;
+        trace!(
+            "evict {} glyphs from atlas page {} at {}",
+            shelf.glyphs.len(),
+            shelf.page,
+            shelf.y
+        );
+        evicted.append(&mut shelf.glyphs);
+        shelf.x = 0;
+        Some(index)
+    }
+}
+
+/// Texture of one atlas page.
+///
+/// Shelves are sub-rectangles of the page. The engine can only replace whole pictures,
+/// modified shelves are written into the page image and the page is uploaded once
+/// in [FontMap::flush].
+struct AtlasPage {
     engine: UiEngineRef,
-    start: u32,
-    pic: Picture,
+    index: usize,
     path: CString,
-    slots: Box<[GlyphInfo; Self::SIZE]>,
+    /// Created on the first upload.
+    pic: Option<Picture>,
+    bmp: Bmp,
+    dirty: bool,
 }
 
-impl GlyphMap {
-    const SIZE: usize = 256;
+impl AtlasPage {
+    fn new(engine: UiEngineRef, index: usize, size: u16) -> Self {
+        let path = format!("#mainui/backend/atlas{index}.bmp");
+        Self {
+            engine,
+            index,
+            path: CString::new(path).unwrap(),
+            pic: None,
+            bmp: Bmp::builder(size, size)
+                .components(Components::RGBA)
+                .build(),
+            dirty: false,
+        }
+    }
 
-    fn new(engine: UiEngineRef, font: &Font, start: u32) -> Self {
-        let (gw, gh) = font.glyph_size();
-        let font = &font.font;
-        let mut slots = Box::new([GlyphInfo::default(); Self::SIZE]);
-        let width = 32 * gw;
-        let height = (Self::SIZE / 32) as u16 * gh;
-        let mut bmp = Bmp::builder(width, height)
-            .components(Components::RGBA)
-            .build();
-        let (mut x, mut y) = (0, 0);
-        let end = start + Self::SIZE as u32;
-        trace!("generate glyph map for {start:04x}:{end:04x}");
-        for i in 0..Self::SIZE {
-            let n = start + i as u32;
-            let c = match char::from_u32(n) {
-                Some(c) if c.is_control() => continue,
-                Some(c) => c,
-                None => continue,
-            };
-            // trace!("generate glyph for '\\u{n:04x}' '{c}'");
-            let glyph = font.scaled_glyph(c);
-            let outline = font
-                .outline_glyph(glyph)
-                .or_else(|| font.outline_glyph(font.scaled_glyph('\u{25a1}')));
-            let Some(outline) = outline else {
-                trace!("skip glyph for '\\u{n:04x}' '{c}'");
-                continue;
-            };
-            let bounds = outline.px_bounds();
-            let w = bounds.width() as u16;
-            if x + w > width {
-                x = 0;
-                y += gh;
-            }
-            outline.draw(|px, py, f| {
-                if f <= 0.0 {
-                    return;
+    fn write(&mut self, info: &GlyphInfo, glyph: &Glyph) {
+        let rows = glyph.coverage.chunks(glyph.w as usize);
+        for (py, row) in rows.enumerate() {
+            let y = self.bmp.height() - (info.y + py as u16) - 1;
+            for (px, &a) in row.iter().enumerate() {
+                if a != 0 {
+                    self.bmp.set_pixel(info.x + px as u16, y, 255, 255, 255, a);
                 }
-                let a = (f * 255.0) as u8;
-                let x = x + px as u16;
-                let y = bmp.height() - (y + py as u16) - 1;
-                bmp.set_pixel(x, y, 255, 255, 255, a);
-            });
-            slots[i] = GlyphInfo::new(x, y, &bounds);
-            x += w;
+            }
         }
+        self.dirty = true;
+    }
 
-        if false {
-            for (i, info) in slots.iter().enumerate() {
-                let i = start + i as u32;
-                let (x, y) = (info.x, info.y);
-                let (w, h) = (info.w, info.h);
-                let (bx, by) = (info.bearing_x, info.bearing_y);
-                trace!("'\\u{i:04x}' {x:3}x{y:<3} {w:3}x{h:<3} {bx:3}x{by:<3x}");
+    /// Clears rows of the shelf.
+    fn clear(&mut self, shelf: &Shelf) {
+        for row in shelf.y..shelf.y + shelf.height {
+            let y = self.bmp.height() - row - 1;
+            for x in 0..self.bmp.width() {
+                self.bmp.set_pixel(x, y, 0, 0, 0, 0);
             }
         }
+        self.dirty = true;
+    }
 
-        let path = format!("#mainui/backend/map{start:04x}.bmp");
+    fn upload(&mut self) {
+        if self.pic.take().is_some() {
+            self.engine.pic_free(&self.path);
+        }
 
         #[cfg(feature = "std")]
         if false {
-            let path = format!("/tmp/map{start:04x}.bmp");
-            std::fs::write(path, bmp.as_slice()).unwrap();
+            let path = format!("/tmp/atlas{}.bmp", self.index);
+            std::fs::write(path, self.bmp.as_slice()).unwrap();
         }
 
-        let path = CString::new(path).unwrap();
-        let pic = engine.pic_create(&path, bmp.as_slice()).unwrap();
-        Self {
-            engine,
-            start,
-            pic,
-            path,
-            slots,
+        self.pic = self.engine.pic_create(&self.path, self.bmp.as_slice()).ok();
+        if self.pic.is_none() {
+            error!("failed to create glyph atlas {:?}", self.path);
         }
-    }
-
-    fn get(&self, i: usize) -> &GlyphInfo {
-        &self.slots[i]
+        self.dirty = false;
     }
 }
 
-impl Drop for GlyphMap {
+impl Drop for AtlasPage {
     fn drop(&mut self) {
-        self.engine.pic_free(&self.path);
+        if self.pic.is_some() {
+            self.engine.pic_free(&self.path);
+        }
     }
 }
 
+/// Glyph cache shared by all characters and styles.
+///
+/// Glyphs are rasterized on demand and packed into shelves of fixed size pages. If
+/// the atlas is full the least recently used shelf is cleared for new glyphs.
 pub struct FontMap {
     engine: UiEngineRef,
     font: Font,
-    map: Vec<GlyphMap>,
+    packer: Packer,
+    /// Textures of packer pages with the same indices.
+    pages: Vec<AtlasPage>,
+    glyphs: BTreeMap<GlyphKey, CachedGlyph>,
+    /// Glyphs that did not fit into the atlas, retried after a shelf is evicted.
+    failed: Vec<GlyphKey>,
+    frame: u32,
+    stats: AtlasStats,
 }
 
 impl FontMap {
     pub fn new(engine: UiEngineRef, font: Font) -> Self {
+        // enough space for a few screens of unique glyphs
+        let (gw, gh) = font.glyph_size();
+        let page_size = (gw.max(gh) as u32 * 16)
+            .next_power_of_two()
+            .clamp(256, 2048) as u16;
         Self {
             engine,
             font,
-            map: Default::default(),
+            packer: Packer::new(page_size, gh),
+            pages: Vec::new(),
+            glyphs: BTreeMap::new(),
+            failed: Vec::new(),
+            frame: 0,
+            stats: AtlasStats::default(),
         }
     }
 
@@ -179,17 +460,168 @@ impl FontMap {
         self.font.glyph_size()
     }
 
-    pub fn get(&mut self, c: char, _: Modifier) -> (Picture, &GlyphInfo) {
-        let start = c as u32 & !(GlyphMap::SIZE as u32 - 1);
-        let index = match self.map.binary_search_by_key(&start, |i| i.start) {
-            Ok(index) => index,
-            Err(index) => {
-                let info = GlyphMap::new(self.engine, &self.font, start);
-                self.map.insert(index, info);
-                index
+    pub fn stats(&self) -> AtlasStats {
+        let size = self.packer.page_size as u32;
+        let pages = self.packer.pages.len();
+        AtlasStats {
+            pages,
+            glyphs: self.glyphs.len(),
+            capacity: size * size * pages as u32,
+            ..self.stats
+        }
+    }
+
+    /// Starts a new frame, glyphs cached in the current frame are never evicted.
+    pub fn begin_frame(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Rasterizes the glyph if it is not in the atlas yet.
+    ///
+    /// Must be called for every glyph before [FontMap::flush] and [FontMap::get].
+    pub fn cache(&mut self, c: char, modifier: Modifier) {
+        let key = (GlyphStyle::from(modifier), c);
+        if let Some(cached) = self.glyphs.get(&key) {
+            self.stats.hits += 1;
+            if let Some(shelf) = cached.shelf {
+                self.packer.touch(shelf, self.frame);
             }
+            return;
+        }
+
+        self.stats.misses += 1;
+        let empty = CachedGlyph {
+            info: GlyphInfo::default(),
+            shelf: None,
+        };
+        let Some(outline) = self.font.outline_glyph(c) else {
+            self.glyphs.insert(key, empty);
+            return;
         };
-        let info = &self.map[index];
-        (info.pic, info.get(c as usize & (GlyphMap::SIZE - 1)))
+        let glyph = Glyph::new(&outline, key.0);
+        let mut evicted = Vec::new();
+        let alloc = self
+            .packer
+            .alloc(key, glyph.w, glyph.h, self.frame, &mut evicted);
+        let Some((shelf, x)) = alloc else {
+            // do not try again until space is freed
+            warn!("glyph atlas is full, skip '\\u{:04x}'", c as u32);
+            self.glyphs.insert(key, empty);
+            self.failed.push(key);
+            return;
+        };
+        if !evicted.is_empty() {
+            self.remove_evicted(shelf, &evicted);
+        }
+
+        let Shelf { page, y, .. } = self.packer.shelves[shelf];
+        let info = GlyphInfo {
+            x,
+            y,
+            w: glyph.w,
+            h: glyph.h,
+            bearing_x: glyph.bearing_x,
+            bearing_y: glyph.bearing_y,
+        };
+        while self.pages.len() < self.packer.pages.len() {
+            let index = self.pages.len();
+            let page = AtlasPage::new(self.engine, index, self.packer.page_size);
+            self.pages.push(page);
+        }
+        self.pages[page].write(&info, &glyph);
+        self.stats.used += glyph.w as u32 * glyph.h as u32;
+        let shelf = Some(shelf);
+        self.glyphs.insert(key, CachedGlyph { info, shelf });
+    }
+
+    /// Removes glyphs evicted from the shelf.
+    fn remove_evicted(&mut self, shelf: usize, evicted: &[GlyphKey]) {
+        for key in evicted {
+            if let Some(cached) = self.glyphs.remove(key) {
+                let info = cached.info;
+                self.stats.used -= info.w as u32 * info.h as u32;
+                self.stats.evictions += 1;
+            }
+        }
+        let shelf = &self.packer.shelves[shelf];
+        self.pages[shelf.page].clear(shelf);
+        // the freed space could fit them now
+        for key in self.failed.drain(..) {
+            self.glyphs.remove(&key);
+        }
+    }
+
+    /// Uploads pages with modified shelves to the engine.
+    pub fn flush(&mut self) {
+        for page in self.pages.iter_mut().filter(|i| i.dirty) {
+            page.upload();
+            self.stats.uploads += 1;
+        }
+    }
+
+    /// Returns the atlas texture and the glyph position in it.
+    pub fn get(&self, c: char, modifier: Modifier) -> Option<(Picture, &GlyphInfo)> {
+        let cached = self.glyphs.get(&(GlyphStyle::from(modifier), c))?;
+        let shelf = &self.packer.shelves[cached.shelf?];
+        Some((self.pages[shelf.page].pic?, &cached.info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> GlyphKey {
+        (GlyphStyle::default(), c)
+    }
+
+    #[test]
+    fn pack_shelves() {
+        let mut packer = Packer::new(16, 8);
+        let mut evicted = Vec::new();
+        assert_eq!(packer.alloc(key('a'), 8, 8, 1, &mut evicted), Some((0, 0)));
+        assert_eq!(packer.alloc(key('b'), 8, 6, 1, &mut evicted), Some((0, 8)));
+        // the first shelf is full
+        assert_eq!(packer.alloc(key('c'), 4, 8, 1, &mut evicted), Some((1, 0)));
+        assert_eq!(packer.shelves[1].y, 8);
+        // a taller shelf does not fit into the first page
+        assert_eq!(packer.alloc(key('d'), 4, 12, 1, &mut evicted), Some((2, 0)));
+        assert_eq!(packer.shelves[2].page, 1);
+        assert_eq!(packer.shelves[2].height, 12);
+        // glyphs larger than a page are never packed
+        assert_eq!(packer.alloc(key('e'), 17, 8, 1, &mut evicted), None);
+        assert!(evicted.is_empty());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut packer = Packer::new(16, 8);
+        let mut evicted = Vec::new();
+        // 4 pages with 2 shelves of 2 glyphs, every shelf is used in its own frame
+        for (i, c) in ('a'..='p').enumerate() {
+            let frame = i as u32 / 2;
+            assert!(packer.alloc(key(c), 8, 8, frame, &mut evicted).is_some());
+        }
+        assert_eq!(packer.pages.len(), Packer::MAX_PAGES);
+        assert!(evicted.is_empty());
+
+        // the oldest shelf is cleared
+        assert_eq!(packer.alloc(key('q'), 8, 8, 8, &mut evicted), Some((0, 0)));
+        assert_eq!(evicted, [key('a'), key('b')]);
+        evicted.clear();
+        assert_eq!(packer.alloc(key('r'), 8, 8, 8, &mut evicted), Some((0, 8)));
+        packer.touch(1, 8);
+        // shelves used in the current frame are kept
+        assert_eq!(packer.alloc(key('s'), 8, 8, 8, &mut evicted), Some((2, 0)));
+        assert_eq!(evicted, [key('e'), key('f')]);
+        evicted.clear();
+
+        for i in 0..packer.shelves.len() {
+            packer.touch(i, 8);
+        }
+        packer.shelves[2].x = 16;
+        assert_eq!(packer.alloc(key('t'), 8, 8, 8, &mut evicted), None);
+        assert!(evicted.is_empty());
+        assert_eq!(packer.pages.len(), Packer::MAX_PAGES);
     }
 }
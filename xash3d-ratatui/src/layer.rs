@@ -0,0 +1,99 @@
+use alloc::{boxed::Box, vec::Vec};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+};
+use xash3d_ui::{color::RGBA, misc::Rect as UiRect, picture::Picture, prelude::*};
+
+/// Engine drawing deferred until the cell buffer is drawn.
+pub enum DrawCommand {
+    /// Fills the area in pixels with a color.
+    Fill { color: RGBA, area: UiRect },
+    /// Draws a part of the picture to the area in pixels.
+    Picture {
+        pic: Picture,
+        color: RGBA,
+        area: UiRect,
+        src: Option<UiRect>,
+    },
+    /// Custom drawing, for example a 3D scene pass.
+    Custom(Box<dyn FnOnce(UiEngineRef)>),
+}
+
+impl DrawCommand {
+    fn draw(self, engine: UiEngineRef) {
+        match self {
+            Self::Fill { color, area } => engine.fill_rgba(color, area),
+            Self::Picture {
+                pic,
+                color,
+                area,
+                src,
+            } => pic.draw(color, area, src),
+            Self::Custom(f) => f(engine),
+        }
+    }
+}
+
+/// Draw commands composited with the cell buffer.
+///
+/// Cells in the layer area are drawn below the commands unless they were changed after
+/// the layer was created, for example by a popup rendered on top of a picture.
+pub struct Layer {
+    area: Rect,
+    /// Cells in the area at the moment the layer was created.
+    snapshot: Vec<Cell>,
+    commands: Vec<DrawCommand>,
+}
+
+impl Layer {
+    pub fn new(area: Rect, buffer: &Buffer, commands: Vec<DrawCommand>) -> Self {
+        let area = area.intersection(buffer.area);
+        Self {
+            area,
+            snapshot: area.positions().map(|pos| buffer[pos].clone()).collect(),
+            commands,
+        }
+    }
+
+    pub(crate) fn draw(self, engine: UiEngineRef) {
+        for command in self.commands {
+            command.draw(engine);
+        }
+    }
+}
+
+/// Returns the number of layers below every cell.
+///
+/// The result is empty if there are no layers.
+pub(crate) fn cell_levels(buffer: &Buffer, layers: &[Layer]) -> Vec<u8> {
+    if layers.is_empty() {
+        return Vec::new();
+    }
+    let mut levels = vec![0; buffer.content().len()];
+    for (i, layer) in layers.iter().enumerate() {
+        let level = u8::try_from(i + 1).unwrap_or(u8::MAX);
+        for (pos, old) in layer.area.positions().zip(&layer.snapshot) {
+            if buffer.area.contains(pos) && buffer[pos] != *old {
+                levels[buffer.index_of(pos.x, pos.y)] = level;
+            }
+        }
+    }
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_changed_after_layer() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        let layer = Layer::new(Rect::new(1, 0, 2, 2), &buffer, Vec::new());
+        // popup over the layer and text outside of it
+        buffer[(2, 1)].set_symbol("x");
+        buffer[(3, 1)].set_symbol("y");
+        let levels = cell_levels(&buffer, &[layer]);
+        assert_eq!(levels, [0, 0, 0, 0, 0, 0, 1, 0]);
+    }
+}
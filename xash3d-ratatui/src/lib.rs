@@ -10,9 +10,23 @@ extern crate std;
 extern crate log;
 
 mod backend;
+mod background;
 mod bmp;
+mod cursor;
+mod dump;
 mod font;
+mod layer;
+mod palette;
 mod terminal;
 
-pub use backend::XashBackend;
+pub use backend::{
+    MAX_FONT_SIZE, MIN_FONT_SIZE, UI_BACKGROUND_ALPHA, UI_DPI, UI_FONT, UI_FONT_SIZE, UI_SCALE,
+    XashBackend,
+};
+pub use background::{UI_BACKGROUND, UI_BACKGROUND_MODE, UI_LOGO, UI_LOGO_POSITION};
+pub use cursor::{UI_CURSOR_BLINK, UI_CURSOR_SHAPE, UI_MOUSE_POINTER};
+pub use dump::{DumpFormat, dump};
+pub use font::{AtlasStats, font_names};
+pub use layer::{DrawCommand, Layer};
+pub use palette::Palette;
 pub use terminal::XashTerminal;
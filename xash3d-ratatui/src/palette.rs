@@ -0,0 +1,151 @@
+use ratatui::{
+    buffer::Cell,
+    style::{Color, Modifier},
+};
+use xash3d_ui::color::RGBA;
+
+/// Colors used to draw cells on the screen.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Palette {
+    /// Base colors in the ANSI order, from [Color::Black] to [Color::White].
+    pub colors: [[u8; 3]; 16],
+    /// Foreground for [Color::Reset].
+    pub fg: [u8; 3],
+    /// Background for [Color::Reset].
+    pub bg: [u8; 3],
+    /// Opacity of backgrounds if the game is visible behind the menu.
+    pub alpha: u8,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            colors: [
+                [0x00, 0x00, 0x00],
+                [0xf4, 0x00, 0x5f],
+                [0x98, 0xe0, 0x24],
+                [0xfa, 0x84, 0x19],
+                [0x9d, 0x65, 0xff],
+                [0xa4, 0x30, 0x7f],
+                [0x58, 0xd1, 0xeb],
+                [0xc4, 0xc5, 0xb5],
+                [0x62, 0x5e, 0x4c],
+                [0xa4, 0x30, 0x5f],
+                [0x98, 0xe0, 0x24],
+                [0xe0, 0xd5, 0x61],
+                [0x9d, 0x65, 0xff],
+                [0xf4, 0x30, 0x7f],
+                [0x58, 0xd1, 0xeb],
+                [0xf6, 0xf6, 0xef],
+            ],
+            fg: [0xf6, 0xf6, 0xef],
+            bg: [0x1a, 0x1a, 0x1a],
+            alpha: 0xff,
+        }
+    }
+}
+
+impl Palette {
+    /// Returns the index of the base color in the palette.
+    pub fn base_index(color: Color) -> Option<usize> {
+        let index = match color {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::Gray => 7,
+            Color::DarkGray => 8,
+            Color::LightRed => 9,
+            Color::LightGreen => 10,
+            Color::LightYellow => 11,
+            Color::LightBlue => 12,
+            Color::LightMagenta => 13,
+            Color::LightCyan => 14,
+            Color::White => 15,
+            _ => return None,
+        };
+        Some(index)
+    }
+
+    pub fn rgb(&self, color: Color, is_fg: bool) -> [u8; 3] {
+        match color {
+            Color::Reset if is_fg => self.fg,
+            Color::Reset => self.bg,
+            Color::Rgb(r, g, b) => [r, g, b],
+            Color::Indexed(i) => self.indexed(i),
+            _ => self.colors[Self::base_index(color).unwrap()],
+        }
+    }
+
+    /// Converts a color from the xterm 256-color palette.
+    fn indexed(&self, index: u8) -> [u8; 3] {
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match index {
+            0..16 => self.colors[index as usize],
+            16..232 => {
+                let i = index - 16;
+                [
+                    CUBE[(i / 36) as usize],
+                    CUBE[(i / 6 % 6) as usize],
+                    CUBE[(i % 6) as usize],
+                ]
+            }
+            _ => {
+                let level = 8 + (index - 232) * 10;
+                [level; 3]
+            }
+        }
+    }
+
+    /// Returns the foreground and the background colors of the cell with applied modifiers.
+    ///
+    /// The flag is `true` if the cell does not use the default background.
+    pub(crate) fn cell_rgb(&self, cell: &Cell) -> ([u8; 3], [u8; 3], bool) {
+        let modifier = cell.modifier;
+        let mut fg = self.rgb(cell.fg, true);
+        let mut bg = self.rgb(cell.bg, false);
+        let mut fill = cell.bg != Color::Reset;
+        if modifier.contains(Modifier::REVERSED) {
+            core::mem::swap(&mut fg, &mut bg);
+            fill = true;
+        }
+        if modifier.contains(Modifier::DIM) {
+            // half way to the background
+            for (fg, bg) in fg.iter_mut().zip(bg) {
+                *fg = ((*fg as u16 + bg as u16) / 2) as u8;
+            }
+        }
+        (fg, bg, fill)
+    }
+
+    /// Returns the foreground and the background colors of the cell with applied modifiers.
+    ///
+    /// The background is `None` if the cell uses the default background.
+    pub(crate) fn cell_colors(&self, cell: &Cell, alpha: u8) -> (RGBA, Option<RGBA>) {
+        let ([r, g, b], bg, fill) = self.cell_rgb(cell);
+        let fg = RGBA::rgb(r, g, b);
+        let [r, g, b] = bg;
+        (fg, fill.then_some(RGBA::new(r, g, b, alpha)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_colors() {
+        let palette = Palette::default();
+        assert_eq!(palette.indexed(1), palette.rgb(Color::Red, true));
+        assert_eq!(palette.indexed(15), palette.rgb(Color::White, false));
+        assert_eq!(palette.indexed(16), [0, 0, 0]);
+        assert_eq!(palette.indexed(21), [0, 0, 255]);
+        assert_eq!(palette.indexed(196), [255, 0, 0]);
+        assert_eq!(palette.indexed(231), [255, 255, 255]);
+        assert_eq!(palette.indexed(232), [8, 8, 8]);
+        assert_eq!(palette.indexed(255), [238, 238, 238]);
+    }
+}
@@ -1,7 +1,11 @@
+use alloc::string::String;
 use ratatui::{buffer::Buffer, layout::Rect};
 use xash3d_ui::prelude::*;
 
-use crate::XashBackend;
+use crate::{
+    XashBackend,
+    dump::{self, DumpFormat},
+};
 
 pub struct XashTerminal {
     backend: XashBackend,
@@ -24,18 +28,45 @@ impl XashTerminal {
         &mut self.backend
     }
 
+    /// Returns the buffer rendered by the last call to [draw](Self::draw).
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Serializes the last rendered buffer with the current palette.
+    pub fn dump(&self, format: DumpFormat) -> String {
+        dump::dump(&self.buffer, self.backend.palette(), format)
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         self.backend.resize(width, height);
         self.buffer.resize(self.backend.area());
     }
 
-    pub fn draw<F>(&mut self, mut render_callback: F)
+    /// Renders widgets to the buffer without drawing it on the screen.
+    pub fn render<F>(&mut self, mut render_callback: F)
     where
         F: FnMut(Rect, &mut Buffer, &mut XashBackend),
     {
-        self.buffer.reset();
         let area = self.backend.area();
+        if self.buffer.area != area {
+            // the font size was changed
+            self.buffer.resize(area);
+        }
+        self.buffer.reset();
         render_callback(area, &mut self.buffer, &mut self.backend);
+    }
+
+    /// Draws the buffer on the screen.
+    pub fn flush(&mut self) {
         self.backend.draw_buffer(&self.buffer);
     }
+
+    pub fn draw<F>(&mut self, render_callback: F)
+    where
+        F: FnMut(Rect, &mut Buffer, &mut XashBackend),
+    {
+        self.render(render_callback);
+        self.flush();
+    }
 }